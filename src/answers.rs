@@ -0,0 +1,140 @@
+use crate::parse::{lines, ParseError};
+use crate::solution::{Answer, Part};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Known-correct answers, keyed by year, day and part
+#[derive(Debug, Eq, PartialEq, Default, Clone)]
+pub struct Answers {
+    answers: HashMap<(u16, u8, Part), Answer>,
+}
+
+/// The result of checking an answer against the known answers
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Verdict {
+    Correct,
+    Incorrect { expected: Answer },
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect { expected } => write!(f, "incorrect, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl Answers {
+    /// Parse an answers file, made of lines like `2024/5/1 = 143`.
+    /// Blank lines and lines starting with `#` are ignored
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut answers = HashMap::new();
+        for line in lines(input) {
+            let text = line.text.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let (key, value) = text
+                .split_once('=')
+                .ok_or_else(|| line.error(text, "expected an answer like `2024/5/1 = 143`"))?;
+            let mut key_parts = key.trim().split('/');
+            let year = line.parse(key_parts.next().unwrap())?;
+            let day = line.parse(
+                key_parts
+                    .next()
+                    .ok_or_else(|| line.error(key, "expected a day"))?,
+            )?;
+            let part = line.parse(
+                key_parts
+                    .next()
+                    .ok_or_else(|| line.error(key, "expected a part"))?,
+            )?;
+            if let Some(extra) = key_parts.next() {
+                return Err(line.error(extra, "expected only a year, day and part"));
+            }
+            let value = value.trim();
+            if value.is_empty() {
+                return Err(line.missing("an answer"));
+            }
+            answers.insert((year, day, part), line.parse(value)?);
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&Answer> {
+        self.answers.get(&(year, day, part))
+    }
+
+    pub fn check(&self, year: u16, day: u8, part: Part, answer: &Answer) -> Verdict {
+        match self.get(year, day, part) {
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Incorrect {
+                expected: expected.clone(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        # Known answers\n\
+        2024/1/1 = 11\n\
+        2024/1/2=31\n\
+        \n\
+        2024/9/1 = hello";
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(EXAMPLE).unwrap();
+        assert_eq!(Some(&Answer::from(11)), answers.get(2024, 1, Part::One));
+        assert_eq!(Some(&Answer::from(31)), answers.get(2024, 1, Part::Two));
+        assert_eq!(
+            Some(&Answer::from("hello")),
+            answers.get(2024, 9, Part::One)
+        );
+        assert_eq!(None, answers.get(2024, 9, Part::Two));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                8,
+                "3",
+                "unknown part 3, expected 1 or 2"
+            )),
+            Answers::parse("2024/1/3 = 11")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 12, "", "expected an answer")),
+            Answers::parse("2024/1/1 = ")
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Verdict::Correct,
+            answers.check(2024, 1, Part::One, &Answer::from(11))
+        );
+        assert_eq!(
+            Verdict::Incorrect {
+                expected: Answer::from(31)
+            },
+            answers.check(2024, 1, Part::Two, &Answer::from(30))
+        );
+        assert_eq!(
+            Verdict::Unknown,
+            answers.check(2024, 2, Part::One, &Answer::from(2))
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::solution::Part;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+/// A separately timed step of solving a puzzle
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+/// Timings of repeated runs of a stage, sorted from fastest to slowest
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Timings {
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+    /// Panics if there are no samples
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples to time");
+        samples.sort();
+        Timings { samples }
+    }
+
+    /// Time `f` over a number of iterations
+    pub fn measure<T, E>(
+        iterations: usize,
+        mut f: impl FnMut() -> Result<T, E>,
+    ) -> Result<Self, E> {
+        let mut samples = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            let result = std::hint::black_box(f()?);
+            samples.push(start.elapsed());
+            drop(result);
+        }
+        Ok(Timings::new(samples))
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn median(&self) -> Duration {
+        self.samples[self.samples.len() / 2]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timings() {
+        let timings = Timings::new(
+            [5, 1, 4, 2, 3]
+                .into_iter()
+                .map(Duration::from_millis)
+                .collect(),
+        );
+        assert_eq!(Duration::from_millis(1), timings.min());
+        assert_eq!(Duration::from_millis(3), timings.median());
+        assert_eq!(Duration::from_millis(5), timings.max());
+    }
+}
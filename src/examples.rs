@@ -0,0 +1,73 @@
+use crate::solution::{Answer, Part, Solver};
+use simple_error::bail;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The answer given for one part of an example, and the answer it should have given
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Checked {
+    /// The (1-based) position of the example in the day's examples
+    pub example: usize,
+    pub part: Part,
+    pub expected: &'static str,
+    pub answer: Answer,
+}
+
+impl Checked {
+    pub fn passed(&self) -> bool {
+        self.answer.to_string() == self.expected
+    }
+}
+
+impl Display for Checked {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "example {} part {}: ", self.example, self.part)?;
+        if self.passed() {
+            write!(f, "{}", self.answer)
+        } else {
+            write!(f, "expected {}, got {}", self.expected, self.answer)
+        }
+    }
+}
+
+/// Solve every part of a day's examples that has an expected answer
+pub fn check_examples(solver: &dyn Solver) -> Result<Vec<Checked>, Box<dyn Error>> {
+    let mut checked = Vec::new();
+    for (i, example) in solver.examples().iter().enumerate() {
+        let parts: Vec<Part> = Part::ALL
+            .into_iter()
+            .filter(|part| example.expected(*part).is_some())
+            .collect();
+        let solved = match solver.solve(example.input, &parts) {
+            Ok(solved) => solved,
+            Err(e) => bail!("example {}: {}", i + 1, e),
+        };
+        for solved in solved {
+            checked.push(Checked {
+                example: i + 1,
+                part: solved.part,
+                expected: example.expected(solved.part).unwrap(),
+                answer: solved.answer,
+            });
+        }
+    }
+    Ok(checked)
+}
+
+/// Generate a test checking the examples of the given [`crate::solution::Solution`]
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ident) => {
+        #[cfg(test)]
+        mod examples {
+            #[test]
+            fn test_examples() {
+                let checked = $crate::examples::check_examples(&super::$solution).unwrap();
+                assert!(!checked.is_empty(), "no examples with expected answers");
+                for checked in checked {
+                    assert!(checked.passed(), "{}", checked);
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,232 @@
+use crate::parse::{lines, ParseError};
+use std::ops::{Index, IndexMut};
+
+pub type Coordinate = (i32, i32);
+
+pub const CARDINAL_DIRECTIONS: [Coordinate; 4] = [
+    (0, -1), // N
+    (1, 0),  // E
+    (0, 1),  // S
+    (-1, 0), // W
+];
+
+pub const INTERCARDINAL_DIRECTIONS: [Coordinate; 4] = [
+    (1, -1),  // NE
+    (1, 1),   // SE
+    (-1, 1),  // SW
+    (-1, -1), // NW
+];
+
+pub const ALL_DIRECTIONS: [Coordinate; 8] = [
+    (0, -1),  // N
+    (1, -1),  // NE
+    (1, 0),   // E
+    (1, 1),   // SE
+    (0, 1),   // S
+    (-1, 1),  // SW
+    (-1, 0),  // W
+    (-1, -1), // NW
+];
+
+/// A rectangular grid of cells, stored row by row
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from its cells in row order.
+    /// Panics if the number of cells doesn't match the dimensions
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "cells don't fit the grid");
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parse a grid from a character map, one row per (non-empty) line.
+    /// `cell` maps each character to a cell, returning `None` if the character is invalid
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(Coordinate, char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in lines(input).filter(|line| !line.text.is_empty()) {
+            let mut row_width = 0;
+            for (i, c) in line.text.char_indices() {
+                let pos = (row_width as i32, height as i32);
+                let token = &line.text[i..i + c.len_utf8()];
+                cells.push(cell(pos, c).ok_or_else(|| line.error(token, "unexpected character"))?);
+                row_width += 1;
+            }
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(line.error(
+                        line.text,
+                        format!("expected a row of width {}, found {}", width, row_width),
+                    ))
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Coordinate) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// Wrap a coordinate around the edges of the grid, so that it's always inside it
+    pub fn wrap(&self, (x, y): Coordinate) -> Coordinate {
+        (
+            x.rem_euclid(self.width as i32),
+            y.rem_euclid(self.height as i32),
+        )
+    }
+
+    fn index_of(&self, pos: Coordinate) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.1 as usize * self.width + pos.0 as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Coordinate) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Coordinate) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Iterate over every cell along with its coordinate, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (((i % width) as i32, (i / width) as i32), cell))
+    }
+
+    /// Iterate over the neighbours of a cell in the given directions,
+    /// skipping any that are outside the grid
+    pub fn neighbours<'a>(
+        &'a self,
+        (x, y): Coordinate,
+        directions: &'a [Coordinate],
+    ) -> impl Iterator<Item = (Coordinate, &'a T)> {
+        directions.iter().filter_map(move |(dx, dy)| {
+            let pos = (x + dx, y + dy);
+            self.get(pos).map(|cell| (pos, cell))
+        })
+    }
+
+    /// The (up to) 4 cells that share an edge with the given cell
+    pub fn cardinal_neighbours(&self, pos: Coordinate) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.neighbours(pos, &CARDINAL_DIRECTIONS)
+    }
+
+    /// The (up to) 8 cells that share an edge or corner with the given cell
+    pub fn all_neighbours(&self, pos: Coordinate) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.neighbours(pos, &ALL_DIRECTIONS)
+    }
+
+    /// Render the grid back to text, one line per row
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        self.cells
+            .chunks(self.width.max(1))
+            .map(|row| row.iter().map(&mut cell).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Coordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Coordinate) -> &Self::Output {
+        self.get(pos).expect("coordinate out of bounds")
+    }
+}
+
+impl<T> IndexMut<Coordinate> for Grid<T> {
+    fn index_mut(&mut self, pos: Coordinate) -> &mut Self::Output {
+        self.get_mut(pos).expect("coordinate out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+        ab.\n\
+        .c#";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(EXAMPLE, |_, c| Some(c)).unwrap();
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(Some(&'b'), grid.get((1, 0)));
+        assert_eq!(Some(&'#'), grid.get((2, 1)));
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!(None, grid.get((0, -1)));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 3, "#", "unexpected character")),
+            Grid::parse(EXAMPLE, |_, c| if c == '#' { None } else { Some(c) })
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                2,
+                1,
+                "abcd",
+                "expected a row of width 3, found 4"
+            )),
+            Grid::parse("abc\nabcd", |_, c| Some(c))
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::parse(EXAMPLE, |_, c| Some(c)).unwrap();
+        assert_eq!(
+            vec![((1, 0), &'b'), ((0, 1), &'.')],
+            grid.cardinal_neighbours((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![((1, 0), &'b'), ((1, 1), &'c'), ((0, 1), &'.')],
+            grid.all_neighbours((0, 0)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::parse(EXAMPLE, |_, c| Some(c == '#')).unwrap();
+        assert_eq!(
+            "...\n..#",
+            grid.render(|wall| if *wall { '#' } else { '.' })
+        );
+    }
+}
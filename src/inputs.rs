@@ -0,0 +1,153 @@
+use simple_error::bail;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the session token used to fetch inputs
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Finds puzzle inputs in a local cache directory,
+/// fetching (and caching) any that are missing
+pub struct InputManager {
+    cache_dir: PathBuf,
+    base_url: String,
+    session: Option<String>,
+}
+
+impl InputManager {
+    pub fn new(
+        cache_dir: impl Into<PathBuf>,
+        base_url: impl Into<String>,
+        session: Option<String>,
+    ) -> Self {
+        InputManager {
+            cache_dir: cache_dir.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session,
+        }
+    }
+
+    /// The cached location of a day's input, e.g. `inputs/2024/day05.txt`
+    pub fn cache_path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day{:02}.txt", day))
+    }
+
+    /// Whether the input can be supplied, either from the cache or by fetching it
+    pub fn is_available(&self, year: u16, day: u8) -> bool {
+        self.session.is_some() || self.cache_path(year, day).exists()
+    }
+
+    /// Get a day's input, from the cache if possible
+    pub fn get(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+        let path = self.cache_path(year, day);
+        if path.exists() {
+            return Ok(fs::read_to_string(path)?);
+        }
+
+        let input = self.fetch(year, day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, &input)?;
+        Ok(input)
+    }
+
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+        let Some(session) = &self.session else {
+            bail!(
+                "no input for {} day {} at {}, and no session token to fetch it (set {})",
+                year,
+                day,
+                self.cache_path(year, day).display(),
+                SESSION_VAR
+            );
+        };
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        match ureq::get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .call()
+        {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, _)) => {
+                bail!("fetching {} failed with status {}", url, status)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Find the session token, either from the environment or the first line of a config file
+pub fn session_token(config: &Path) -> Option<String> {
+    std::env::var(SESSION_VAR)
+        .ok()
+        .or_else(|| fs::read_to_string(config).ok())
+        .map(|token| token.lines().next().unwrap_or_default().trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{serve_once, temp_dir};
+
+    #[test]
+    fn test_cache_hit() {
+        let dir = temp_dir("inputs-cache-hit");
+        let inputs = InputManager::new(&dir, "http://127.0.0.1:9", None);
+        fs::create_dir_all(dir.join("2024")).unwrap();
+        fs::write(dir.join("2024/day05.txt"), "cached").unwrap();
+
+        assert!(inputs.is_available(2024, 5));
+        assert_eq!("cached", inputs.get(2024, 5).unwrap());
+    }
+
+    #[test]
+    fn test_fetch() {
+        let dir = temp_dir("inputs-fetch");
+        let (base_url, server) = serve_once(200, "1 2\n3 4\n");
+        let inputs = InputManager::new(&dir, base_url, Some("secret".to_string()));
+
+        assert_eq!("1 2\n3 4\n", inputs.get(2024, 1).unwrap());
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("session=secret"));
+
+        // The input is cached, so the (now stopped) server isn't needed again
+        assert_eq!(
+            "1 2\n3 4\n",
+            fs::read_to_string(dir.join("2024/day01.txt")).unwrap()
+        );
+        assert_eq!("1 2\n3 4\n", inputs.get(2024, 1).unwrap());
+    }
+
+    #[test]
+    fn test_fetch_error() {
+        let dir = temp_dir("inputs-fetch-error");
+        let (base_url, server) = serve_once(404, "Not Found");
+        let inputs = InputManager::new(&dir, &base_url, Some("secret".to_string()));
+
+        assert_eq!(
+            format!(
+                "fetching {}/2024/day/1/input failed with status 404",
+                base_url
+            ),
+            inputs.get(2024, 1).unwrap_err().to_string()
+        );
+        server.join().unwrap();
+        assert!(!dir.join("2024/day01.txt").exists());
+    }
+
+    #[test]
+    fn test_no_session() {
+        let dir = temp_dir("inputs-no-session");
+        let inputs = InputManager::new(&dir, DEFAULT_BASE_URL, None);
+
+        assert!(!inputs.is_available(2024, 1));
+        assert!(inputs.get(2024, 1).is_err());
+    }
+}
@@ -0,0 +1,313 @@
+//! An interpreter for instructions like `mul(2,4)` hidden in corrupted memory
+
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+/// An instruction the tokenizer recognises, written like `name(1,23)`
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Instruction {
+    pub name: &'static str,
+    /// How many arguments the instruction takes
+    pub arity: usize,
+    /// How many digits each (unsigned integer) argument may have
+    pub digits: RangeInclusive<usize>,
+}
+
+pub const MUL: Instruction = Instruction {
+    name: "mul",
+    arity: 2,
+    digits: 1..=3,
+};
+
+pub const DO: Instruction = Instruction::without_args("do");
+
+pub const DONT: Instruction = Instruction::without_args("don't");
+
+impl Instruction {
+    /// An instruction written like `name()`, which has no arguments to constrain
+    pub const fn without_args(name: &'static str) -> Self {
+        Instruction {
+            name,
+            arity: 0,
+            digits: 0..=0,
+        }
+    }
+
+    /// Parse the arguments between the instruction's brackets, if they're valid
+    fn parse_args(&self, args: &str) -> Option<Vec<i64>> {
+        let args: Vec<&str> = match args {
+            "" => Vec::new(),
+            _ => args.split(',').collect(),
+        };
+        if args.len() != self.arity {
+            return None;
+        }
+        args.into_iter()
+            .map(|arg| {
+                let valid = self.digits.contains(&arg.len())
+                    && arg.bytes().all(|byte| byte.is_ascii_digit());
+                valid.then(|| arg.parse().ok()).flatten()
+            })
+            .collect()
+    }
+}
+
+/// An instruction found in the input
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Token {
+    /// The byte offset of the instruction in the input
+    pub offset: usize,
+    /// The index of the matching instruction, in the order they were registered
+    pub instruction: usize,
+    pub name: &'static str,
+    pub args: Vec<i64>,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", self.name, self.args.iter().join(","))
+    }
+}
+
+/// The state instructions are executed against
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct State {
+    pub enabled: bool,
+    pub total: i64,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            enabled: true,
+            total: 0,
+        }
+    }
+}
+
+/// Whether an instruction had an effect when it was reached
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Status {
+    Executed,
+    Skipped,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Executed => write!(f, "executed"),
+            Status::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+/// Carries out an instruction, given its arguments
+pub type Handler = Box<dyn Fn(&mut State, &[i64]) -> Status>;
+
+/// Add the product of the arguments to the total, unless disabled
+pub fn mul(state: &mut State, args: &[i64]) -> Status {
+    if !state.enabled {
+        return Status::Skipped;
+    }
+    state.total += args.iter().product::<i64>();
+    Status::Executed
+}
+
+pub fn enable(state: &mut State, _args: &[i64]) -> Status {
+    state.enabled = true;
+    Status::Executed
+}
+
+pub fn disable(state: &mut State, _args: &[i64]) -> Status {
+    state.enabled = false;
+    Status::Executed
+}
+
+/// An instruction that was reached, and what became of it
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Step {
+    pub token: Token,
+    pub status: Status,
+}
+
+/// The final state after running the input, and every instruction that was reached
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Run {
+    pub state: State,
+    pub trace: Vec<Step>,
+}
+
+/// Runs the registered instructions found in the input, ignoring everything else
+#[derive(Default)]
+pub struct Interpreter {
+    instructions: Vec<(Instruction, Handler)>,
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Interpreter::default()
+    }
+
+    /// Recognise an instruction, which is carried out by the given handler
+    pub fn register(
+        &mut self,
+        instruction: Instruction,
+        handler: impl Fn(&mut State, &[i64]) -> Status + 'static,
+    ) {
+        self.instructions.push((instruction, Box::new(handler)));
+    }
+
+    /// The index, arguments and length of the registered instruction at the start of `input`
+    fn match_start(&self, input: &str) -> Option<(usize, Vec<i64>, usize)> {
+        self.instructions
+            .iter()
+            .enumerate()
+            .find_map(|(i, (instruction, _))| {
+                let rest = input.strip_prefix(instruction.name)?.strip_prefix('(')?;
+                let (args, _) = rest.split_once(')')?;
+                let len = instruction.name.len() + args.len() + "()".len();
+                Some((i, instruction.parse_args(args)?, len))
+            })
+    }
+
+    /// Scan the input for registered instructions, in order
+    pub fn tokenize(&self, input: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut offset = 0;
+        while offset < input.len() {
+            let rest = &input[offset..];
+            match self.match_start(rest) {
+                Some((i, args, len)) => {
+                    tokens.push(Token {
+                        offset,
+                        instruction: i,
+                        name: self.instructions[i].0.name,
+                        args,
+                    });
+                    offset += len;
+                }
+                None => offset += rest.chars().next().unwrap().len_utf8(),
+            }
+        }
+        tokens
+    }
+
+    /// Carry out every registered instruction in the input, in order
+    pub fn run(&self, input: &str) -> Run {
+        let mut state = State::default();
+        let trace = self
+            .tokenize(input)
+            .into_iter()
+            .map(|token| {
+                let (_, handler) = &self.instructions[token.instruction];
+                let status = handler(&mut state, &token.args);
+                Step { token, status }
+            })
+            .collect();
+        Run { state, trace }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(offset: usize, instruction: usize, name: &'static str, args: &[i64]) -> Token {
+        Token {
+            offset,
+            instruction,
+            name,
+            args: args.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let mut interpreter = Interpreter::new();
+        interpreter.register(MUL, mul);
+        interpreter.register(DO, enable);
+        assert_eq!(
+            vec![
+                token(0, 0, "mul", &[2, 4]),
+                token(19, 1, "do", &[]),
+                token(27, 0, "mul", &[999, 1]),
+            ],
+            interpreter.tokenize("mul(2,4)mul(1234,5)do()mul(mul(999,1)mul(1,2,3)do(1)")
+        );
+    }
+
+    #[test]
+    fn test_constraints() {
+        let mut interpreter = Interpreter::new();
+        interpreter.register(
+            Instruction {
+                name: "neg",
+                arity: 1,
+                digits: 2..=2,
+            },
+            |state, args| {
+                state.total -= args[0];
+                Status::Executed
+            },
+        );
+        let run = interpreter.run("neg(1)neg(12)neg(123)neg(-12)neg(12,3)neg(05)");
+        assert_eq!(
+            vec![token(6, 0, "neg", &[12]), token(38, 0, "neg", &[5])],
+            run.trace
+                .into_iter()
+                .map(|step| step.token)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(-17, run.state.total);
+    }
+
+    #[test]
+    fn test_same_name() {
+        let mut interpreter = Interpreter::new();
+        interpreter.register(MUL, mul);
+        interpreter.register(
+            Instruction {
+                name: "mul",
+                arity: 3,
+                digits: 1..=3,
+            },
+            |state, _args| {
+                state.total += 1000;
+                Status::Executed
+            },
+        );
+        let run = interpreter.run("mul(1,2,3)");
+        assert_eq!(
+            vec![token(0, 1, "mul", &[1, 2, 3])],
+            run.trace
+                .into_iter()
+                .map(|step| step.token)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(1000, run.state.total);
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut interpreter = Interpreter::new();
+        interpreter.register(MUL, mul);
+        interpreter.register(DO, enable);
+        interpreter.register(DONT, disable);
+        let run = interpreter.run("ü mul(2,3)don't()mul(4,5)do()mul(1,1)");
+        assert_eq!(
+            vec![
+                (3, Status::Executed),
+                (11, Status::Executed),
+                (18, Status::Skipped),
+                (26, Status::Executed),
+                (30, Status::Executed),
+            ],
+            run.trace
+                .iter()
+                .map(|step| (step.token.offset, step.status))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(7, run.state.total);
+    }
+}
@@ -0,0 +1,16 @@
+pub mod answers;
+pub mod bench;
+pub mod examples;
+pub mod grid;
+pub mod inputs;
+pub mod interpreter;
+pub mod parse;
+pub mod pattern;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+#[cfg(test)]
+mod testing;
+pub mod word_search;
+pub mod year2024;
+pub mod years;
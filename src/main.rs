@@ -1,35 +1,474 @@
-use clap::Parser;
+use advent2024::answers::{Answers, Verdict};
+use advent2024::examples::check_examples;
+use advent2024::inputs::{session_token, InputManager, DEFAULT_BASE_URL};
+use advent2024::scaffold::scaffold;
+use advent2024::solution::{Answer, Part, Solved, Solver};
+use advent2024::submit::{History, Submitter};
+use advent2024::years::{self, latest_year, parse_year, Days, YEARS};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use itertools::Itertools;
 use simple_error::bail;
 use std::error::Error;
 use std::fs;
-
-mod year2024;
+use std::io::{self, IsTerminal, Read};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 struct Cli {
-    input_file: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day's puzzle (or every day's, with `--all`)
+    Run(RunArgs),
+    /// List the registered years and days
+    List,
+    /// Time parsing and each part of a day's puzzle over a number of runs
+    Bench(BenchArgs),
+    /// Check the solutions against the examples from the puzzle descriptions
+    Test(TestArgs),
+    /// Create a new day's module from a template, and register it
+    New(NewArgs),
+    /// Submit an answer to the site
+    Submit(SubmitArgs),
+}
+
+#[derive(Args)]
+struct YearArgs {
+    #[arg(short, long, default_value_t = latest_year(), value_parser = parse_year)]
+    year: u16,
+}
+
+#[derive(Args)]
+struct DayArgs {
+    #[command(flatten)]
+    year: YearArgs,
 
     #[arg(short, long)]
-    year: String,
+    day: u8,
+}
+
+/// Where inputs are cached, and how to talk to the site
+#[derive(Args)]
+struct SiteArgs {
+    /// Directory containing (cached) inputs, named like `2024/day05.txt`
+    #[arg(long, default_value = "inputs")]
+    inputs: PathBuf,
+
+    /// Where to fetch inputs from and submit answers to
+    #[arg(long, default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+
+    /// File containing the session token, if `AOC_SESSION` isn't set
+    #[arg(long, default_value = ".session")]
+    session_file: PathBuf,
+}
+
+impl SiteArgs {
+    fn input_manager(&self) -> InputManager {
+        InputManager::new(
+            &self.inputs,
+            &self.base_url,
+            session_token(&self.session_file),
+        )
+    }
+}
+
+/// Where to read a day's puzzle input from
+#[derive(Args)]
+struct InputArgs {
+    /// Puzzle input file, or `-` to read from stdin.
+    /// If absent, input is piped from stdin, or (if nothing is piped) taken from the inputs directory,
+    /// fetching it if needed
+    input_file: Option<String>,
+
+    /// Use the given text as the puzzle input, instead of reading a file
+    #[arg(long, conflicts_with = "input_file")]
+    input_text: Option<String>,
+
+    #[command(flatten)]
+    site: SiteArgs,
+}
+
+impl InputArgs {
+    fn read(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+        if let Some(text) = &self.input_text {
+            return Ok(text.clone());
+        }
+        match self.input_file.as_deref() {
+            Some("-") => read_stdin(),
+            Some(path) => Ok(fs::read_to_string(path)?),
+            None => {
+                // Only use piped input if there is some, as stdin isn't a terminal
+                // whenever we're run non-interactively, e.g. from cron or CI
+                if !io::stdin().is_terminal() {
+                    let input = read_stdin()?;
+                    if !input.trim().is_empty() {
+                        return Ok(input);
+                    }
+                }
+                self.site.input_manager().get(year, day)
+            }
+        }
+    }
+}
 
+#[derive(Args)]
+struct RunArgs {
+    #[command(flatten)]
+    year: YearArgs,
+
+    #[arg(short, long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    #[arg(short, long, value_enum, default_value_t = PartArg::All)]
+    part: PartArg,
+
+    /// Run every registered day of the year, reading inputs from `--inputs`
+    #[arg(long, conflicts_with_all = ["input_file", "input_text"])]
+    all: bool,
+
+    /// Check the answers against a file of known answers, made of lines like `2024/5/1 = 143`
+    #[arg(long, value_name = "FILE")]
+    check: Option<PathBuf>,
+
+    /// Explain how the input is solved instead of printing the answers, if the day supports it
+    #[arg(long, conflicts_with_all = ["all", "check"])]
+    explain: bool,
+
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    day: DayArgs,
+
+    #[arg(short, long, value_enum, default_value_t = PartArg::All)]
+    part: PartArg,
+
+    /// How many times to run each stage
+    #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+#[derive(Args)]
+struct TestArgs {
+    #[command(flatten)]
+    year: YearArgs,
+
+    /// Only check this day's examples, rather than every registered day's
     #[arg(short, long)]
+    day: Option<u8>,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    #[command(flatten)]
+    year: YearArgs,
+
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
     day: u8,
+
+    /// The crate's source directory
+    #[arg(long, default_value = "src")]
+    src: PathBuf,
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let cli = Cli::parse();
+#[derive(Args)]
+struct SubmitArgs {
+    #[command(flatten)]
+    day: DayArgs,
+
+    #[arg(short, long)]
+    part: Part,
 
-    let input = fs::read_to_string(cli.input_file)?;
+    /// The answer to submit. If absent, the part is solved using the input from `--inputs`
+    answer: Option<String>,
+
+    /// File recording every submitted answer and its outcome
+    #[arg(long, default_value = "submissions.tsv")]
+    history: PathBuf,
+
+    #[command(flatten)]
+    site: SiteArgs,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum PartArg {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    All,
+}
+
+impl PartArg {
+    fn parts(&self) -> &'static [Part] {
+        match self {
+            PartArg::One => &[Part::One],
+            PartArg::Two => &[Part::Two],
+            PartArg::All => &Part::ALL,
+        }
+    }
+}
+
+fn get_days(year: u16) -> Result<&'static Days, Box<dyn Error>> {
+    match years::get_days(year) {
+        Some(days) => Ok(days),
+        None => bail!("Unknown year {}", year),
+    }
+}
+
+fn get_solver(year: u16, day: u8) -> Result<&'static dyn Solver, Box<dyn Error>> {
+    match years::get_solver(year, day) {
+        Some(solver) => Ok(solver),
+        None => bail!("Unknown day {}", day),
+    }
+}
 
-    let func = match cli.year.as_str() {
-        "2024" => year2024::DAYS.get(&cli.day),
-        _ => bail!("Unknown year {}", cli.year),
+fn read_stdin() -> Result<String, Box<dyn Error>> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Check an answer against the known answers, if there are any
+fn check(answers: Option<&Answers>, year: u16, day: u8, solved: &Solved) -> Option<Verdict> {
+    answers.map(|answers| answers.check(year, day, solved.part, &solved.answer))
+}
+
+fn ensure_correct<'a>(
+    verdicts: impl Iterator<Item = &'a Option<Verdict>>,
+) -> Result<(), Box<dyn Error>> {
+    let incorrect = verdicts
+        .filter(|verdict| matches!(verdict, Some(Verdict::Incorrect { .. })))
+        .count();
+    if incorrect > 0 {
+        bail!("{} answer(s) did not match the known answers", incorrect);
+    }
+    Ok(())
+}
+
+fn run_all(args: &RunArgs, answers: Option<&Answers>) -> Result<(), Box<dyn Error>> {
+    let year = args.year.year;
+    let days = get_days(year)?;
+    let inputs = args.input.site.input_manager();
+
+    let mut rows: Vec<(u8, Solved, Option<Verdict>)> = Vec::new();
+    let mut failed = 0;
+    for day in days.keys().copied().sorted() {
+        if !inputs.is_available(year, day) {
+            eprintln!(
+                "skipping day {}: no input at {}",
+                day,
+                inputs.cache_path(year, day).display()
+            );
+            continue;
+        }
+        let input = match inputs.get(year, day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping day {}: {}", day, e);
+                continue;
+            }
+        };
+        let solved = match days[&day].solve(&input, args.part.parts()) {
+            Ok(solved) => solved,
+            Err(e) => {
+                eprintln!("day {}: {}", day, e);
+                failed += 1;
+                continue;
+            }
+        };
+        for solved in solved {
+            let verdict = check(answers, year, day, &solved);
+            rows.push((day, solved, verdict));
+        }
+    }
+
+    let answer_width = rows
+        .iter()
+        .map(|(_, solved, _)| solved.answer.to_string().len())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap();
+    println!(
+        "Day  Part  {:<answer_width$}  {:<10}  Check",
+        "Answer", "Time"
+    );
+    for (day, solved, verdict) in &rows {
+        println!(
+            "{:>3}  {:>4}  {:<answer_width$}  {:<10}  {}",
+            day,
+            solved.part.to_string(),
+            solved.answer.to_string(),
+            format!("{:.2?}", solved.elapsed),
+            verdict.as_ref().map_or("-".to_string(), Verdict::to_string)
+        );
+    }
+
+    ensure_correct(rows.iter().map(|(_, _, verdict)| verdict))?;
+    if failed > 0 {
+        bail!("{} day(s) failed to parse their input", failed);
+    }
+    Ok(())
+}
+
+fn run(args: &RunArgs) -> Result<(), Box<dyn Error>> {
+    let answers = match &args.check {
+        Some(path) => Some(Answers::parse(&fs::read_to_string(path)?)?),
+        None => None,
+    };
+
+    if args.all {
+        return run_all(args, answers.as_ref());
+    }
+
+    let Some(day) = args.day else {
+        unreachable!("clap requires a day unless running all days")
+    };
+    let year = args.year.year;
+
+    let solver = get_solver(year, day)?;
+    let input = args.input.read(year, day)?;
+
+    if args.explain {
+        match solver.explain(&input)? {
+            Some(explanation) => println!("{}", explanation),
+            None => bail!("Day {} can't explain its input", day),
+        }
+        return Ok(());
+    }
+
+    let mut verdicts = Vec::new();
+    for solved in solver.solve(&input, args.part.parts())? {
+        let verdict = check(answers.as_ref(), year, day, &solved);
+        match &verdict {
+            Some(verdict) => println!(
+                "part {} solution: {} ({})",
+                solved.part, solved.answer, verdict
+            ),
+            None => println!("part {} solution: {}", solved.part, solved.answer),
+        }
+        verdicts.push(verdict);
+    }
+
+    ensure_correct(verdicts.iter())
+}
+
+fn list() -> Result<(), Box<dyn Error>> {
+    for (year, days) in YEARS.entries().sorted_by_key(|(year, _)| **year) {
+        println!("{}: {}", year, days.keys().sorted().join(" "));
+    }
+    Ok(())
+}
+
+fn bench(args: &BenchArgs) -> Result<(), Box<dyn Error>> {
+    let (year, day) = (args.day.year.year, args.day.day);
+    let solver = get_solver(year, day)?;
+    let input = args.input.read(year, day)?;
+
+    let results = solver.bench(&input, args.part.parts(), args.iterations as usize)?;
+    println!("Stage   {:>10}  {:>10}  {:>10}", "Min", "Median", "Max");
+    for (stage, timings) in results {
+        println!(
+            "{:<6}  {:>10}  {:>10}  {:>10}",
+            stage.to_string(),
+            format!("{:.2?}", timings.min()),
+            format!("{:.2?}", timings.median()),
+            format!("{:.2?}", timings.max())
+        );
+    }
+
+    Ok(())
+}
+
+fn test(args: &TestArgs) -> Result<(), Box<dyn Error>> {
+    let year = args.year.year;
+    let days = match args.day {
+        Some(day) => vec![day],
+        None => get_days(year)?.keys().copied().sorted().collect(),
     };
 
-    match func {
-        Some(f) => f(&input),
-        None => bail!("Unknown day {}", cli.day),
+    let mut failed = 0;
+    for day in days {
+        let checked = match check_examples(get_solver(year, day)?) {
+            Ok(checked) => checked,
+            Err(e) => {
+                failed += 1;
+                println!("day {}: FAIL ({})", day, e);
+                continue;
+            }
+        };
+        let passed = checked.iter().filter(|checked| checked.passed()).count();
+        if checked.is_empty() {
+            println!("day {}: no examples", day);
+        } else if passed == checked.len() {
+            println!("day {}: pass ({}/{})", day, passed, checked.len());
+        } else {
+            failed += 1;
+            println!("day {}: FAIL ({}/{})", day, passed, checked.len());
+            for checked in checked.iter().filter(|checked| !checked.passed()) {
+                println!("  {}", checked);
+            }
+        }
     }
 
+    if failed > 0 {
+        bail!("{} day(s) failed their examples", failed);
+    }
+    Ok(())
+}
+
+fn new(args: &NewArgs) -> Result<(), Box<dyn Error>> {
+    let path = scaffold(&args.src, args.year.year, args.day)?;
+    println!("created {}", path.display());
     Ok(())
 }
+
+fn submit(args: &SubmitArgs) -> Result<(), Box<dyn Error>> {
+    let (year, day) = (args.day.year.year, args.day.day);
+    let Some(session) = session_token(&args.site.session_file) else {
+        bail!("No session token to submit with (set AOC_SESSION)");
+    };
+
+    let answer: Answer = match &args.answer {
+        Some(answer) => answer.parse()?,
+        None => {
+            let solver = get_solver(year, day)?;
+            let input = args.site.input_manager().get(year, day)?;
+            let solved = solver.solve(&input, &[args.part])?;
+            solved[0].answer.clone()
+        }
+    };
+
+    let history = History::load(&args.history)?;
+    let mut submitter = Submitter::new(&args.site.base_url, session, history);
+    let outcome = submitter.submit(year, day, args.part, &answer)?;
+    println!(
+        "{} day {} part {}: submitted {}, {}",
+        year, day, args.part, answer, outcome
+    );
+
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    match &cli.command {
+        Command::Run(args) => run(args),
+        Command::List => list(),
+        Command::Bench(args) => bench(args),
+        Command::Test(args) => test(args),
+        Command::New(args) => new(args),
+        Command::Submit(args) => submit(args),
+    }
+}
@@ -0,0 +1,129 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An error encountered while parsing puzzle input,
+/// with the (1-based) location of the offending text
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, reason: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.reason
+        )?;
+        if !self.text.is_empty() {
+            write!(f, " (`{}`)", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// A single line of puzzle input
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Line<'a> {
+    /// 1-based line number
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// The (1-based) column of a token, which must be a slice of this line.
+    /// Anything else is treated as being at the end of the line
+    fn column(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+        if offset <= self.text.len() && self.text.is_char_boundary(offset) {
+            self.text[..offset].chars().count() + 1
+        } else {
+            self.text.chars().count() + 1
+        }
+    }
+
+    /// Create an error pointing at the given token in this line
+    pub fn error(&self, token: &str, reason: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.column(token), token, reason)
+    }
+
+    /// Create an error pointing at the end of this line,
+    /// for when something was expected but the line ran out
+    pub fn missing(&self, expected: &str) -> ParseError {
+        ParseError::new(
+            self.number,
+            self.text.chars().count() + 1,
+            "",
+            format!("expected {}", expected),
+        )
+    }
+
+    /// Parse a token in this line, pointing at it if it is invalid
+    pub fn parse<T>(&self, token: &'a str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        token
+            .parse()
+            .map_err(|e: T::Err| self.error(token, e.to_string()))
+    }
+}
+
+/// Iterate over every line of the input (including empty ones)
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let line = lines("1 2\n3 x4").nth(1).unwrap();
+        let token = line.text.split_whitespace().nth(1).unwrap();
+        assert_eq!(
+            Err(ParseError::new(2, 3, "x4", "invalid digit found in string")),
+            line.parse::<u32>(token)
+        );
+    }
+
+    #[test]
+    fn test_missing() {
+        let line = lines("12").next().unwrap();
+        assert_eq!(
+            ParseError::new(1, 3, "", "expected a number"),
+            line.missing("a number")
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "line 2, column 3: invalid digit found in string (`x4`)",
+            ParseError::new(2, 3, "x4", "invalid digit found in string").to_string()
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use crate::grid::{Coordinate, Grid};
+use crate::parse::ParseError;
+
+/// A small 2D shape to look for in a grid of characters
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct Pattern {
+    /// `None` cells match any character
+    cells: Grid<Option<char>>,
+}
+
+impl Pattern {
+    /// Parse a pattern from a block of text, one row per line, with `.` as a wildcard
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        Ok(Pattern {
+            cells: Grid::parse(text, |_, c| Some((c != '.').then_some(c)))?,
+        })
+    }
+
+    /// Build a pattern of the given size, taking each cell from this one
+    fn transform(
+        &self,
+        width: usize,
+        height: usize,
+        from: impl Fn(Coordinate) -> Coordinate,
+    ) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height as i32 {
+            for x in 0..width as i32 {
+                cells.push(self.cells[from((x, y))]);
+            }
+        }
+        Pattern {
+            cells: Grid::new(width, height, cells),
+        }
+    }
+
+    /// The pattern turned a quarter turn clockwise
+    pub fn rotated(&self) -> Self {
+        let (width, height) = (self.cells.width(), self.cells.height());
+        self.transform(height, width, |(x, y)| (y, height as i32 - 1 - x))
+    }
+
+    /// The pattern mirrored left to right
+    pub fn reflected(&self) -> Self {
+        let width = self.cells.width();
+        self.transform(width, self.cells.height(), |(x, y)| {
+            (width as i32 - 1 - x, y)
+        })
+    }
+
+    /// The distinct versions of the pattern, optionally including its rotations and reflections.
+    /// The pattern itself always comes first
+    pub fn variants(&self, rotations: bool, reflections: bool) -> Vec<Pattern> {
+        let mut variants = vec![self.clone()];
+        if reflections {
+            variants.push(self.reflected());
+        }
+        if rotations {
+            for i in 0..variants.len() {
+                let mut rotated = variants[i].clone();
+                for _ in 0..3 {
+                    rotated = rotated.rotated();
+                    variants.push(rotated.clone());
+                }
+            }
+        }
+        let mut distinct: Vec<Pattern> = Vec::new();
+        for variant in variants {
+            if !distinct.contains(&variant) {
+                distinct.push(variant);
+            }
+        }
+        distinct
+    }
+
+    /// Whether the pattern matches with its top left corner at `pos`
+    pub fn matches_at(&self, grid: &Grid<char>, (x, y): Coordinate) -> bool {
+        self.cells.iter().all(|((dx, dy), cell)| match cell {
+            None => grid.contains((x + dx, y + dy)),
+            Some(c) => grid.get((x + dx, y + dy)) == Some(c),
+        })
+    }
+
+    /// Every position (of the top left corner) where the pattern matches, row by row
+    pub fn find(&self, grid: &Grid<char>) -> Vec<Coordinate> {
+        grid.iter()
+            .map(|(pos, _)| pos)
+            .filter(|pos| self.matches_at(grid, *pos))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(text: &str) -> Pattern {
+        Pattern::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_transform() {
+        let l = pattern(
+            "\
+            a.\n\
+            bc\n\
+            d.",
+        );
+        assert_eq!(
+            pattern(
+                "\
+                dba\n\
+                .c."
+            ),
+            l.rotated()
+        );
+        assert_eq!(l, l.rotated().rotated().rotated().rotated());
+        assert_eq!(
+            pattern(
+                "\
+                .a\n\
+                cb\n\
+                .d"
+            ),
+            l.reflected()
+        );
+    }
+
+    #[test]
+    fn test_variants() {
+        let l = pattern(
+            "\
+            a.\n\
+            bc",
+        );
+        assert_eq!(vec![l.clone()], l.variants(false, false));
+        assert_eq!(4, l.variants(true, false).len());
+        assert_eq!(2, l.variants(false, true).len());
+        assert_eq!(8, l.variants(true, true).len());
+        // Symmetrical patterns have fewer distinct variants
+        assert_eq!(1, pattern("a").variants(true, true).len());
+        assert_eq!(2, pattern("ab").variants(false, true).len());
+        assert_eq!(4, pattern("ab").variants(true, true).len());
+        assert_eq!(2, pattern("aa").variants(true, true).len());
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::parse(
+            "\
+            abab\n\
+            cbcb\n\
+            abaa",
+            |_, c| Some(c),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![(0, 0), (2, 0), (0, 1), (2, 1)],
+            pattern(
+                "\
+                .b\n\
+                .."
+            )
+            .find(&grid)
+        );
+        // Wildcards still have to be inside the grid
+        assert_eq!(
+            vec![(0, 1)],
+            pattern(
+                "\
+                c.c.\n\
+                ...."
+            )
+            .find(&grid)
+        );
+        assert!(pattern("abc").find(&grid).is_empty());
+    }
+
+    #[test]
+    fn test_multi_byte() {
+        let grid = Grid::parse(
+            "\
+            ŘōŁ\n\
+            œ☃ü",
+            |_, c| Some(c),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![(1, 0)],
+            pattern(
+                "\
+                ō.\n\
+                .ü"
+            )
+            .find(&grid)
+        );
+        assert_eq!(
+            vec![(1, 0)],
+            pattern(
+                "\
+                ü☃\n\
+                Łō"
+            )
+            .rotated()
+            .rotated()
+            .find(&grid)
+        );
+        // Ř is U+0158, whose low byte is X
+        assert!(pattern("X").find(&grid).is_empty());
+    }
+}
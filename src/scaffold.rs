@@ -0,0 +1,210 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use simple_error::bail;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+lazy_static! {
+    static ref MOD_REGEX: Regex = Regex::new(r"^mod day(\d+);$").unwrap();
+    static ref ENTRY_REGEX: Regex = Regex::new(r"^\s*(\d+)_u8 => &day\d+::Day\d+,$").unwrap();
+}
+
+/// The source of a new, empty day module
+pub fn day_template(year: u16, day: u8) -> String {
+    format!(
+        r#"//! https://adventofcode.com/{year}/day/{day}
+
+use crate::parse::{{lines, ParseError}};
+use crate::solution::{{Answer, Example, Solution}};
+
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {{
+    Ok(lines(input)
+        .filter(|line| !line.text.is_empty())
+        .map(|line| line.text.to_string())
+        .collect())
+}}
+
+fn part_1(input: &[String]) -> usize {{
+    input.len()
+}}
+
+fn part_2(input: &[String]) -> usize {{
+    input.len()
+}}
+
+const EXAMPLE: &str = "";
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    type Input = Vec<String>;
+
+    // The example test fails until the puzzle's answers are filled in
+    const EXAMPLES: &'static [Example] = &[Example {{
+        input: EXAMPLE,
+        part_1: None,
+        part_2: None,
+    }}];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {{
+        parse_input(input)
+    }}
+
+    fn part_1(input: &Self::Input) -> Answer {{
+        part_1(input).into()
+    }}
+
+    fn part_2(input: &Self::Input) -> Answer {{
+        part_2(input).into()
+    }}
+}}
+
+crate::example_tests!(Day{day});
+"#
+    )
+}
+
+/// Insert `line` before the first of `lines` matched by `regex` whose day sorts after `day`
+/// (comparing the days by `key`), or after the last match if there isn't one
+fn insert_sorted<K: Ord>(
+    lines: &mut Vec<String>,
+    regex: &Regex,
+    key: impl Fn(u8) -> K,
+    day: u8,
+    line: String,
+) -> Result<(), Box<dyn Error>> {
+    let mut index = None;
+    for (i, existing) in lines.iter().enumerate() {
+        let Some(captures) = regex.captures(existing) else {
+            continue;
+        };
+        let existing_day: u8 = captures[1].parse()?;
+        if existing_day == day {
+            bail!("day {} is already registered", day);
+        }
+        index = Some(i + 1);
+        if key(existing_day) > key(day) {
+            index = Some(i);
+            break;
+        }
+    }
+    let Some(index) = index else {
+        bail!("found nowhere to register day {}", day);
+    };
+    lines.insert(index, line);
+    Ok(())
+}
+
+/// Add a day's `mod` declaration and `DAYS` entry to the source of a year module,
+/// keeping the entries in day order and the declarations in the order rustfmt sorts them
+pub fn register_day(registry: &str, day: u8) -> Result<String, Box<dyn Error>> {
+    let mut lines: Vec<String> = registry.lines().map(str::to_string).collect();
+    insert_sorted(
+        &mut lines,
+        &MOD_REGEX,
+        |day| format!("day{}", day),
+        day,
+        format!("mod day{};", day),
+    )?;
+    insert_sorted(
+        &mut lines,
+        &ENTRY_REGEX,
+        |day| day,
+        day,
+        format!("    {}_u8 => &day{}::Day{},", day, day, day),
+    )?;
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Write a new day module under `src_dir` and register it with its year,
+/// refusing to overwrite a day that already exists. Returns the path of the new module
+pub fn scaffold(src_dir: &Path, year: u16, day: u8) -> Result<PathBuf, Box<dyn Error>> {
+    let registry_path = src_dir.join(format!("year{}.rs", year));
+    let day_path = src_dir
+        .join(format!("year{}", year))
+        .join(format!("day{}.rs", day));
+    if day_path.exists() {
+        bail!("{} already exists", day_path.display());
+    }
+    if !registry_path.exists() {
+        bail!("no module for {} at {}", year, registry_path.display());
+    }
+
+    let registry = register_day(&fs::read_to_string(&registry_path)?, day)?;
+    fs::write(&day_path, day_template(year, day))?;
+    fs::write(&registry_path, registry)?;
+    Ok(day_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_dir;
+
+    const REGISTRY: &str = "\
+        use crate::solution::Solver;\n\
+        use phf::phf_map;\n\
+        \n\
+        mod day1;\n\
+        mod day3;\n\
+        \n\
+        pub static DAYS: phf::Map<u8, &'static dyn Solver> = phf_map! {\n\
+        \x20   1_u8 => &day1::Day1,\n\
+        \x20   3_u8 => &day3::Day3,\n\
+        };\n";
+
+    #[test]
+    fn test_register_day() {
+        assert_eq!(
+            "\
+            use crate::solution::Solver;\n\
+            use phf::phf_map;\n\
+            \n\
+            mod day1;\n\
+            mod day2;\n\
+            mod day3;\n\
+            \n\
+            pub static DAYS: phf::Map<u8, &'static dyn Solver> = phf_map! {\n\
+            \x20   1_u8 => &day1::Day1,\n\
+            \x20   2_u8 => &day2::Day2,\n\
+            \x20   3_u8 => &day3::Day3,\n\
+            };\n",
+            register_day(REGISTRY, 2).unwrap()
+        );
+
+        let registered = register_day(REGISTRY, 10).unwrap();
+        assert!(registered.contains("mod day1;\nmod day10;\nmod day3;\n"));
+        assert!(registered.contains("&day3::Day3,\n    10_u8 => &day10::Day10,\n"));
+    }
+
+    #[test]
+    fn test_register_existing_day() {
+        assert_eq!(
+            "day 3 is already registered",
+            register_day(REGISTRY, 3).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let dir = temp_dir("scaffold");
+        fs::create_dir_all(dir.join("year2024")).unwrap();
+        fs::write(dir.join("year2024.rs"), REGISTRY).unwrap();
+
+        let path = scaffold(&dir, 2024, 2).unwrap();
+        assert_eq!(dir.join("year2024/day2.rs"), path);
+        let source = fs::read_to_string(&path).unwrap();
+        assert!(source.starts_with("//! https://adventofcode.com/2024/day/2\n"));
+        assert!(source.contains("impl Solution for Day2"));
+        assert!(source.contains("part_1: None,\n        part_2: None,"));
+        assert!(fs::read_to_string(dir.join("year2024.rs"))
+            .unwrap()
+            .contains("2_u8 => &day2::Day2,"));
+
+        // Neither the module nor the registry are touched again
+        fs::write(&path, "edited").unwrap();
+        assert!(scaffold(&dir, 2024, 2).is_err());
+        assert_eq!("edited", fs::read_to_string(&path).unwrap());
+    }
+}
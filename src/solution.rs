@@ -0,0 +1,212 @@
+use crate::bench::{Stage, Timings};
+use crate::parse::ParseError;
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// The answer to one part of a puzzle
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_integer!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    /// Anything that looks like an integer is treated as one, otherwise it's text
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.parse()
+            .map(Answer::Integer)
+            .unwrap_or_else(|_| Answer::Text(s.to_string())))
+    }
+}
+
+/// One of the two parts of a day's puzzle
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("unknown part {}, expected 1 or 2", s)),
+        }
+    }
+}
+
+/// An example input from a puzzle's description, with the answers it gives (if known).
+/// Answers are written as they are displayed, so they can be compared against any [`Answer`]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Example {
+    pub input: &'static str,
+    pub part_1: Option<&'static str>,
+    pub part_2: Option<&'static str>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.part_1,
+            Part::Two => self.part_2,
+        }
+    }
+}
+
+/// A solution to a single day's puzzle
+pub trait Solution {
+    /// The puzzle input, after it has been parsed
+    type Input;
+
+    /// Examples from the puzzle's description, checked by [`crate::example_tests`]
+    const EXAMPLES: &'static [Example] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_1(input: &Self::Input) -> Answer;
+
+    fn part_2(input: &Self::Input) -> Answer;
+
+    fn part(input: &Self::Input, part: Part) -> Answer {
+        match part {
+            Part::One => Self::part_1(input),
+            Part::Two => Self::part_2(input),
+        }
+    }
+
+    /// A human-readable breakdown of how the input is solved, for debugging.
+    /// Days without one return `None`
+    fn explain(_input: &Self::Input) -> Option<String> {
+        None
+    }
+}
+
+/// The answer to one part of a puzzle, and how long it took to solve
+#[derive(Debug, Clone)]
+pub struct Solved {
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// Object safe wrapper around [`Solution`], so days can be stored in a registry
+pub trait Solver: Sync {
+    /// Parse the input once, then solve only the requested parts (in order)
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Vec<Solved>, ParseError>;
+
+    /// Time parsing and each of the requested parts separately, over a number of iterations
+    fn bench(
+        &self,
+        input: &str,
+        parts: &[Part],
+        iterations: usize,
+    ) -> Result<Vec<(Stage, Timings)>, ParseError>;
+
+    fn examples(&self) -> &'static [Example];
+
+    /// Parse the input and explain it, see [`Solution::explain`]
+    fn explain(&self, input: &str) -> Result<Option<String>, ParseError>;
+}
+
+impl<S> Solver for S
+where
+    S: Solution + Sync,
+{
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Vec<Solved>, ParseError> {
+        let input = S::parse(input)?;
+        Ok(parts
+            .iter()
+            .map(|part| {
+                let start = Instant::now();
+                let answer = S::part(&input, *part);
+                Solved {
+                    part: *part,
+                    answer,
+                    elapsed: start.elapsed(),
+                }
+            })
+            .collect())
+    }
+
+    fn bench(
+        &self,
+        input: &str,
+        parts: &[Part],
+        iterations: usize,
+    ) -> Result<Vec<(Stage, Timings)>, ParseError> {
+        let mut results = vec![(
+            Stage::Parse,
+            Timings::measure(iterations, || S::parse(input))?,
+        )];
+        let input = S::parse(input)?;
+        for part in parts {
+            let timings =
+                Timings::measure(iterations, || Ok::<_, ParseError>(S::part(&input, *part)))?;
+            results.push((Stage::Part(*part), timings));
+        }
+        Ok(results)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        S::EXAMPLES
+    }
+
+    fn explain(&self, input: &str) -> Result<Option<String>, ParseError> {
+        Ok(S::explain(&S::parse(input)?))
+    }
+}
@@ -0,0 +1,462 @@
+use crate::solution::{Answer, Part};
+use lazy_static::lazy_static;
+use regex::Regex;
+use simple_error::bail;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+lazy_static! {
+    static ref WAIT_REGEX: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+}
+
+/// What the site made of a submitted answer
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, try again after this many seconds
+    Wait(u64),
+    /// The part has already been solved (or isn't unlocked yet)
+    WrongLevel,
+    Unknown,
+}
+
+impl Outcome {
+    /// Work out the outcome from the text of the response page
+    pub fn parse(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Outcome::Correct
+        } else if response.contains("That's not the right answer") {
+            if response.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if response.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Incorrect
+            }
+        } else if let Some(g) = WAIT_REGEX.captures(response) {
+            let minutes: u64 = g.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+            let seconds: u64 = g[2].parse().unwrap();
+            Outcome::Wait(minutes * 60 + seconds)
+        } else if response.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    /// Whether the answer was definitely wrong
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+            Outcome::TooHigh => write!(f, "too-high"),
+            Outcome::TooLow => write!(f, "too-low"),
+            Outcome::Wait(seconds) => write!(f, "wait-{}", seconds),
+            Outcome::WrongLevel => write!(f, "wrong-level"),
+            Outcome::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "incorrect" => Ok(Outcome::Incorrect),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "wrong-level" => Ok(Outcome::WrongLevel),
+            "unknown" => Ok(Outcome::Unknown),
+            _ => s
+                .strip_prefix("wait-")
+                .and_then(|seconds| seconds.parse().ok())
+                .map(Outcome::Wait)
+                .ok_or_else(|| format!("unknown outcome {}", s)),
+        }
+    }
+}
+
+/// A single submitted answer
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Attempt {
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    pub outcome: Outcome,
+}
+
+/// Why an answer wasn't submitted
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Refusal {
+    AlreadySolved { answer: Answer },
+    KnownWrong,
+    TooHigh { bound: Answer },
+    TooLow { bound: Answer },
+    RateLimited { seconds: u64 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "already solved, the answer is {}", answer)
+            }
+            Refusal::KnownWrong => write!(f, "this answer is already known to be wrong"),
+            Refusal::TooHigh { bound } => write!(f, "{} is already known to be too high", bound),
+            Refusal::TooLow { bound } => write!(f, "{} is already known to be too low", bound),
+            Refusal::RateLimited { seconds } => {
+                write!(f, "submitted too recently, wait another {}s", seconds)
+            }
+        }
+    }
+}
+
+impl Error for Refusal {}
+
+/// Every answer that has been submitted, stored one per line as tab separated
+/// `timestamp  year/day/part  answer  outcome`
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Load the history, which is empty if the file doesn't exist yet
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, Box<dyn Error>> {
+        let path = path.into();
+        let mut attempts = Vec::new();
+        if path.exists() {
+            for (i, line) in fs::read_to_string(&path)?.lines().enumerate() {
+                if line.is_empty() {
+                    continue;
+                }
+                attempts.push(Self::parse_attempt(line).ok_or_else(|| {
+                    format!("{}:{}: invalid attempt `{}`", path.display(), i + 1, line)
+                })?);
+            }
+        }
+        Ok(History { path, attempts })
+    }
+
+    fn parse_attempt(line: &str) -> Option<Attempt> {
+        let mut fields = line.split('\t');
+        let timestamp = fields.next()?.parse().ok()?;
+        let mut key = fields.next()?.split('/');
+        let (year, day, part) = (
+            key.next()?.parse().ok()?,
+            key.next()?.parse().ok()?,
+            key.next()?.parse().ok()?,
+        );
+        let answer = fields.next()?.parse().ok()?;
+        let outcome = fields.next()?.parse().ok()?;
+        Some(Attempt {
+            timestamp,
+            year,
+            day,
+            part,
+            answer,
+            outcome,
+        })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Add an attempt, appending it to the history file
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), Box<dyn Error>> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(
+            file,
+            "{}\t{}/{}/{}\t{}\t{}",
+            attempt.timestamp,
+            attempt.year,
+            attempt.day,
+            attempt.part,
+            attempt.answer,
+            attempt.outcome
+        )?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// Check whether an answer is worth submitting, given the previous attempts
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+        now: u64,
+    ) -> Result<(), Refusal> {
+        // The site's rate limit applies to every puzzle
+        if let Some(Attempt {
+            timestamp,
+            outcome: Outcome::Wait(seconds),
+            ..
+        }) = self.attempts.last()
+        {
+            if timestamp + seconds > now {
+                return Err(Refusal::RateLimited {
+                    seconds: timestamp + seconds - now,
+                });
+            }
+        }
+
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|a| a.year == year && a.day == day && a.part == part);
+        for attempt in attempts {
+            match (&attempt.outcome, &attempt.answer, answer) {
+                (Outcome::Correct, correct, _) => {
+                    return Err(Refusal::AlreadySolved {
+                        answer: correct.clone(),
+                    })
+                }
+                (outcome, wrong, answer) if outcome.is_wrong() && wrong == answer => {
+                    return Err(Refusal::KnownWrong)
+                }
+                (Outcome::TooHigh, Answer::Integer(bound), Answer::Integer(n)) if n > bound => {
+                    return Err(Refusal::TooHigh {
+                        bound: attempt.answer.clone(),
+                    })
+                }
+                (Outcome::TooLow, Answer::Integer(bound), Answer::Integer(n)) if n < bound => {
+                    return Err(Refusal::TooLow {
+                        bound: attempt.answer.clone(),
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Posts answers to the site, guarded by the history of previous attempts
+pub struct Submitter {
+    base_url: String,
+    session: String,
+    history: History,
+}
+
+impl Submitter {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>, history: History) -> Self {
+        Submitter {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            history,
+        }
+    }
+
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// Submit an answer, unless the history shows it would be pointless
+    pub fn submit(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+    ) -> Result<Outcome, Box<dyn Error>> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.history.check(year, day, part, answer, now)?;
+
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let response = match ureq::post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ]) {
+            Ok(response) => response.into_string()?,
+            Err(ureq::Error::Status(status, _)) => {
+                bail!("submitting to {} failed with status {}", url, status)
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let outcome = Outcome::parse(&response);
+        self.history.record(Attempt {
+            timestamp: now,
+            year,
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+        })?;
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{serve_once, temp_dir};
+
+    fn attempt(part: Part, answer: i32, outcome: Outcome) -> Attempt {
+        Attempt {
+            timestamp: 1000,
+            year: 2024,
+            day: 1,
+            part,
+            answer: answer.into(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_parse_outcome() {
+        assert_eq!(
+            Outcome::Correct,
+            Outcome::parse("<p>That's the right answer! You are one gold star closer.</p>")
+        );
+        assert_eq!(
+            Outcome::TooHigh,
+            Outcome::parse("<p>That's not the right answer; your answer is too high.</p>")
+        );
+        assert_eq!(
+            Outcome::TooLow,
+            Outcome::parse("<p>That's not the right answer; your answer is too low.</p>")
+        );
+        assert_eq!(
+            Outcome::Incorrect,
+            Outcome::parse("<p>That's not the right answer.</p>")
+        );
+        assert_eq!(
+            Outcome::Wait(95),
+            Outcome::parse("<p>You gave an answer too recently. You have 1m 35s left to wait.</p>")
+        );
+        assert_eq!(
+            Outcome::Wait(12),
+            Outcome::parse("<p>You gave an answer too recently. You have 12s left to wait.</p>")
+        );
+        assert_eq!(
+            Outcome::WrongLevel,
+            Outcome::parse("<p>You don't seem to be solving the right level.</p>")
+        );
+        assert_eq!(Outcome::Unknown, Outcome::parse("<p>???</p>"));
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let path = temp_dir("submit-history").join("history.tsv");
+        let mut history = History::load(&path).unwrap();
+        history
+            .record(attempt(Part::One, 10, Outcome::TooLow))
+            .unwrap();
+        history
+            .record(attempt(Part::One, 20, Outcome::Wait(30)))
+            .unwrap();
+
+        assert_eq!(
+            "1000\t2024/1/1\t10\ttoo-low\n1000\t2024/1/1\t20\twait-30\n",
+            fs::read_to_string(&path).unwrap()
+        );
+        assert_eq!(history.attempts(), History::load(&path).unwrap().attempts());
+    }
+
+    #[test]
+    fn test_check() {
+        let path = temp_dir("submit-check").join("history.tsv");
+        let mut history = History::load(&path).unwrap();
+        history
+            .record(attempt(Part::One, 10, Outcome::TooLow))
+            .unwrap();
+        history
+            .record(attempt(Part::One, 50, Outcome::TooHigh))
+            .unwrap();
+        history
+            .record(attempt(Part::One, 30, Outcome::Incorrect))
+            .unwrap();
+
+        let check = |history: &History, answer: i32| {
+            history.check(2024, 1, Part::One, &answer.into(), 2000)
+        };
+        assert_eq!(Ok(()), check(&history, 20));
+        assert_eq!(Err(Refusal::KnownWrong), check(&history, 30));
+        assert_eq!(Err(Refusal::KnownWrong), check(&history, 10));
+        assert_eq!(
+            Err(Refusal::TooLow { bound: 10.into() }),
+            check(&history, 5)
+        );
+        assert_eq!(
+            Err(Refusal::TooHigh { bound: 50.into() }),
+            check(&history, 51)
+        );
+        // Other parts are unaffected
+        assert_eq!(Ok(()), history.check(2024, 1, Part::Two, &5.into(), 2000));
+
+        history
+            .record(attempt(Part::Two, 40, Outcome::Correct))
+            .unwrap();
+        assert_eq!(
+            Err(Refusal::AlreadySolved { answer: 40.into() }),
+            history.check(2024, 1, Part::Two, &41.into(), 2000)
+        );
+
+        history
+            .record(attempt(Part::One, 20, Outcome::Wait(60)))
+            .unwrap();
+        assert_eq!(
+            Err(Refusal::RateLimited { seconds: 30 }),
+            history.check(2024, 1, Part::One, &20.into(), 1030)
+        );
+        assert_eq!(Ok(()), check(&history, 20));
+    }
+
+    #[test]
+    fn test_submit() {
+        let dir = temp_dir("submit-post");
+        let (base_url, server) = serve_once(
+            200,
+            "<p>That's not the right answer; your answer is too low.</p>",
+        );
+        let history = History::load(dir.join("history.tsv")).unwrap();
+        let mut submitter = Submitter::new(base_url, "secret", history);
+
+        assert_eq!(
+            Outcome::TooLow,
+            submitter.submit(2024, 1, Part::Two, &12.into()).unwrap()
+        );
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.contains("session=secret"));
+        assert!(request.ends_with("level=2&answer=12"));
+        assert_eq!(1, submitter.history().attempts().len());
+
+        // Resubmitting a lower answer is refused without contacting the (stopped) server
+        let error = submitter
+            .submit(2024, 1, Part::Two, &11.into())
+            .unwrap_err();
+        assert_eq!("12 is already known to be too low", error.to_string());
+    }
+}
@@ -0,0 +1,257 @@
+use crate::grid::{Coordinate, Grid};
+
+/// An occurrence of a word in the grid, reading from `start` in `direction`
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct Match {
+    pub word: String,
+    pub start: Coordinate,
+    pub direction: Coordinate,
+}
+
+/// Searches a grid of letters for words, in a set of directions.
+/// Letters are compared as `char`s, so each cell holds one `char` rather than a grapheme cluster
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WordSearch {
+    pub directions: Vec<Coordinate>,
+    /// Whether words can run off one edge of the grid and continue from the opposite edge
+    pub wrap: bool,
+    pub ignore_case: bool,
+}
+
+impl WordSearch {
+    pub fn new(directions: &[Coordinate]) -> Self {
+        WordSearch {
+            directions: directions.to_vec(),
+            wrap: false,
+            ignore_case: false,
+        }
+    }
+
+    /// The next cell in a direction, if there is one
+    fn step<T>(
+        &self,
+        grid: &Grid<T>,
+        (x, y): Coordinate,
+        (dx, dy): Coordinate,
+    ) -> Option<Coordinate> {
+        let pos = (x + dx, y + dy);
+        if self.wrap {
+            Some(grid.wrap(pos))
+        } else {
+            grid.contains(pos).then_some(pos)
+        }
+    }
+
+    fn letters_match(&self, a: char, b: char) -> bool {
+        a == b || self.ignore_case && a.to_lowercase().eq(b.to_lowercase())
+    }
+
+    /// Whether the word can be read from `start` in `direction`
+    fn reads(
+        &self,
+        grid: &Grid<char>,
+        word: &str,
+        start: Coordinate,
+        direction: Coordinate,
+    ) -> bool {
+        let mut pos = Some(start);
+        for letter in word.chars() {
+            match pos {
+                Some(p) if self.letters_match(grid[p], letter) => {
+                    pos = self.step(grid, p, direction);
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Find every occurrence of the words, ordered by where they start (row by row),
+    /// then by word, then by direction
+    pub fn find(&self, grid: &Grid<char>, words: &[&str]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        for (start, _) in grid.iter() {
+            for word in words.iter().filter(|word| !word.is_empty()) {
+                for direction in &self.directions {
+                    if self.reads(grid, word, start, *direction) {
+                        matches.push(Match {
+                            word: word.to_string(),
+                            start,
+                            direction: *direction,
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    /// The cells a match covers, in reading order
+    pub fn cells<T>(&self, grid: &Grid<T>, found: &Match) -> Vec<Coordinate> {
+        let mut cells = vec![found.start];
+        for _ in 1..found.word.chars().count() {
+            let last = *cells.last().unwrap();
+            cells.push(
+                self.step(grid, last, found.direction)
+                    .expect("match is off the grid"),
+            );
+        }
+        cells
+    }
+
+    /// Render the grid with every cell that isn't part of a match replaced by `mask`
+    pub fn render(&self, grid: &Grid<char>, matches: &[Match], mask: char) -> String {
+        let mut masked = Grid::new(
+            grid.width(),
+            grid.height(),
+            vec![mask; grid.width() * grid.height()],
+        );
+        for found in matches {
+            for pos in self.cells(grid, found) {
+                masked[pos] = grid[pos];
+            }
+        }
+        masked.render(|c| *c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{ALL_DIRECTIONS, CARDINAL_DIRECTIONS, INTERCARDINAL_DIRECTIONS};
+
+    const EXAMPLE: &str = "\
+        CAT.\n\
+        OO.A\n\
+        GOD.\n\
+        ..TD";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(EXAMPLE, |_, c| Some(c)).unwrap()
+    }
+
+    fn found(word: &str, start: Coordinate, direction: Coordinate) -> Match {
+        Match {
+            word: word.to_string(),
+            start,
+            direction,
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(
+            vec![
+                found("CAT", (0, 0), (1, 0)),
+                found("COG", (0, 0), (0, 1)),
+                found("GOD", (0, 2), (1, 0)),
+                found("DOG", (2, 2), (-1, 0)),
+            ],
+            WordSearch::new(&CARDINAL_DIRECTIONS).find(&grid(), &["CAT", "COG", "GOD", "DOG"])
+        );
+        assert_eq!(
+            vec![found("COD", (0, 0), (1, 1))],
+            WordSearch::new(&INTERCARDINAL_DIRECTIONS).find(&grid(), &["COD", "CAT"])
+        );
+        // Each word once, with DOC reading COD backwards
+        assert_eq!(
+            6,
+            WordSearch::new(&ALL_DIRECTIONS)
+                .find(&grid(), &["CAT", "COG", "GOD", "DOG", "COD", "DOC"])
+                .len()
+        );
+    }
+
+    #[test]
+    fn test_wrap() {
+        let search = WordSearch {
+            wrap: true,
+            ..WordSearch::new(&CARDINAL_DIRECTIONS)
+        };
+        assert_eq!(
+            vec![found("AO", (1, 0), (0, 1)), found("AO", (3, 1), (1, 0))],
+            search.find(&grid(), &["AO"])
+        );
+        assert_eq!(
+            vec![found("AO", (1, 0), (0, 1))],
+            WordSearch::new(&CARDINAL_DIRECTIONS).find(&grid(), &["AO"])
+        );
+        assert_eq!(
+            vec![found("DC", (3, 3), (1, 1))],
+            WordSearch {
+                wrap: true,
+                ..WordSearch::new(&[(1, 1)])
+            }
+            .find(&grid(), &["DC"])
+        );
+    }
+
+    #[test]
+    fn test_ignore_case() {
+        let search = WordSearch {
+            ignore_case: true,
+            ..WordSearch::new(&CARDINAL_DIRECTIONS)
+        };
+        assert_eq!(
+            vec![found("cat", (0, 0), (1, 0))],
+            search.find(&grid(), &["cat"])
+        );
+        assert!(WordSearch::new(&CARDINAL_DIRECTIONS)
+            .find(&grid(), &["cat"])
+            .is_empty());
+    }
+
+    #[test]
+    fn test_multi_byte() {
+        let grid = Grid::parse(
+            "\
+            łAżÉ\n\
+            ßéŹ.",
+            |_, c| Some(c),
+        )
+        .unwrap();
+        let search = WordSearch::new(&ALL_DIRECTIONS);
+        assert_eq!(
+            vec![found("łA", (0, 0), (1, 0)), found("żé", (2, 0), (-1, 1))],
+            search.find(&grid, &["łA", "żé", "BA"])
+        );
+        let search = WordSearch {
+            ignore_case: true,
+            ..search
+        };
+        assert_eq!(
+            vec![
+                found("éŻ", (3, 0), (-1, 0)),
+                found("éŻ", (1, 1), (1, -1)),
+                found("źé", (2, 1), (1, -1)),
+                found("źé", (2, 1), (-1, 0)),
+            ],
+            search.find(&grid, &["źé", "éŻ"])
+        );
+        assert_eq!(
+            "\
+            ..żÉ\n\
+            .é..",
+            search.render(&grid, &search.find(&grid, &["żé", "éŻ"]), '.')
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let search = WordSearch::new(&CARDINAL_DIRECTIONS);
+        let grid = grid();
+        let matches = search.find(&grid, &["COG", "GOD"]);
+        assert_eq!(
+            "\
+            C...\n\
+            O...\n\
+            GOD.\n\
+            ....",
+            search.render(&grid, &matches, '.')
+        );
+        assert_eq!(
+            vec![(0, 2), (1, 2), (2, 2)],
+            search.cells(&grid, &matches[1])
+        );
+    }
+}
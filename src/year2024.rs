@@ -1,3 +1,4 @@
+use crate::solution::Solver;
 use phf::phf_map;
 
 mod day1;
@@ -10,14 +11,14 @@ mod day7;
 mod day8;
 mod day9;
 
-pub static DAYS: phf::Map<u8, fn(&str)> = phf_map! {
-    1_u8 => day1::run,
-    2_u8 => day2::run,
-    3_u8 => day3::run,
-    4_u8 => day4::run,
-    5_u8 => day5::run,
-    6_u8 => day6::run,
-    7_u8 => day7::run,
-    8_u8 => day8::run,
-    9_u8 => day9::run,
+pub static DAYS: phf::Map<u8, &'static dyn Solver> = phf_map! {
+    1_u8 => &day1::Day1,
+    2_u8 => &day2::Day2,
+    3_u8 => &day3::Day3,
+    4_u8 => &day4::Day4,
+    5_u8 => &day5::Day5,
+    6_u8 => &day6::Day6,
+    7_u8 => &day7::Day7,
+    8_u8 => &day8::Day8,
+    9_u8 => &day9::Day9,
 };
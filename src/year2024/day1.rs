@@ -1,47 +1,164 @@
 //! https://adventofcode.com/2024/day/1
 
-use std::collections::HashMap;
-
-fn parse_input(input: &str) -> (Vec<i32>, Vec<i32>) {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.split_whitespace().collect::<Vec<&str>>())
-        .map(|parts| {
-            (
-                parts[0].parse::<i32>().unwrap(),
-                parts[1].parse::<i32>().unwrap(),
-            )
-        })
-        .unzip()
-}
+use crate::parse::{lines, ParseError};
+use crate::solution::{Answer, Example, Solution};
+use itertools::Itertools;
 
-fn part_1(input: &str) -> i32 {
-    let (mut left, mut right) = parse_input(input);
+/// The numbers in each column of the input, top to bottom
+type Columns = Vec<Vec<i32>>;
 
-    left.sort();
-    right.sort();
+/// Parse lines of whitespace separated numbers into columns.
+/// There must be at least two columns, and every line must have as many as the first
+fn parse_input(input: &str) -> Result<Columns, ParseError> {
+    let mut columns: Columns = Vec::new();
+    for line in lines(input).filter(|line| !line.text.trim().is_empty()) {
+        let tokens: Vec<&str> = line.text.split_whitespace().collect();
+        if tokens.len() < 2 {
+            return Err(line.missing("a second number"));
+        }
+        if columns.is_empty() {
+            columns = vec![Vec::new(); tokens.len()];
+        }
+        if tokens.len() < columns.len() {
+            return Err(line.missing(&format!("{} numbers", columns.len())));
+        }
+        if let Some(extra) = tokens.get(columns.len()) {
+            return Err(line.error(extra, format!("expected only {} numbers", columns.len())));
+        }
+        for (column, token) in columns.iter_mut().zip(tokens) {
+            column.push(line.parse(token)?);
+        }
+    }
+    if columns.is_empty() {
+        return Err(ParseError::new(1, 1, "", "expected two columns of numbers"));
+    }
+    Ok(columns)
+}
 
+/// The total distance between two columns, pairing up their numbers smallest to largest
+fn distance(left: &[i32], right: &[i32]) -> u64 {
     left.iter()
-        .zip(right)
-        .map(|(left, right)| left.abs_diff(right) as i32)
+        .sorted()
+        .zip(right.iter().sorted())
+        .map(|(left, right)| left.abs_diff(*right) as u64)
         .sum()
 }
 
-fn part_2(input: &str) -> i32 {
-    let (left, right) = parse_input(input);
-
-    let occurrences: HashMap<&i32, i32> = right.iter().fold(HashMap::new(), |mut map, number| {
-        map.insert(number, *map.get(number).unwrap_or(&0) + 1);
-        map
-    });
-
+/// The sum of each number in `left`, weighted by how often it appears in `right`
+fn similarity(left: &[i32], right: &[i32]) -> i64 {
+    let occurrences = right.iter().counts();
     left.iter()
-        .map(|number| *number * *occurrences.get(number).unwrap_or(&0))
+        .map(|number| *number as i64 * *occurrences.get(number).unwrap_or(&0) as i64)
         .sum()
 }
 
-pub fn run(input: &str) {
-    println!("part 1 solution: {}", part_1(input));
-    println!("part 2 solution: {}", part_2(input));
+fn part_1(columns: &Columns) -> u64 {
+    distance(&columns[0], &columns[1])
+}
+
+fn part_2(columns: &Columns) -> i64 {
+    similarity(&columns[0], &columns[1])
+}
+
+const EXAMPLE: &str = "\
+    3   4\n\
+    4   3\n\
+    2   5\n\
+    1   3\n\
+    3   9\n\
+    3   3";
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Columns;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_1: Some("11"),
+        part_2: Some("31"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
+}
+
+crate::example_tests!(Day1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            vec![vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]],
+            parse_input(EXAMPLE).unwrap()
+        );
+        assert_eq!(
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+            parse_input("1 2 3\n\n4 5 6\n").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_input_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 2, "", "expected a second number")),
+            parse_input("1 2\n3")
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 4, "", "expected 3 numbers")),
+            parse_input("1 2 3\n4 5")
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 5, "6", "expected only 2 numbers")),
+            parse_input("1 2\n4 5 6")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 3, "x", "invalid digit found in string")),
+            parse_input("1 x")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 1, "", "expected two columns of numbers")),
+            parse_input("")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 1, "", "expected two columns of numbers")),
+            parse_input("\n  \n")
+        );
+    }
+
+    #[test]
+    fn test_distance() {
+        let columns = parse_input(EXAMPLE).unwrap();
+        assert_eq!(11, distance(&columns[0], &columns[1]));
+        assert_eq!(11, distance(&columns[1], &columns[0]));
+        assert_eq!(0, distance(&columns[0], &columns[0]));
+    }
+
+    #[test]
+    fn test_similarity() {
+        let columns = parse_input(EXAMPLE).unwrap();
+        assert_eq!(31, similarity(&columns[0], &columns[1]));
+        // 4 appears once on the left, and 3 three times
+        assert_eq!(4 + 3 * 3 * 3, similarity(&columns[1], &columns[0]));
+    }
+
+    #[test]
+    fn test_column_pairs() {
+        let columns = parse_input("1 3 7\n2 1 7\n3 2 1").unwrap();
+        assert_eq!(0, distance(&columns[0], &columns[1]));
+        assert_eq!(4 + 5, distance(&columns[1], &columns[2]));
+        assert_eq!(1, similarity(&columns[0], &columns[2]));
+    }
 }
@@ -1,40 +1,297 @@
 //! https://adventofcode.com/2024/day/2
 
-fn parse_input<'a>(input: &'a str) -> impl Iterator<Item = Vec<i64>> + use<'a> {
-    input.lines().filter(|line| !line.is_empty()).map(|line| {
-        line.split_whitespace()
-            .map(|str| str.parse().unwrap())
-            .collect::<Vec<i64>>()
-    })
-}
-
-fn valid_report(report: &Vec<i64>) -> bool {
-    let diffs: Vec<i64> = report.windows(2).map(|w| w[0] - w[1]).collect();
-    // Any two adjacent levels differ by at least one and at most three.
-    let within_range = diffs.iter().all(|diff| (1..=3).contains(&diff.abs()));
-    // The levels are either all increasing or all decreasing.
-    let same_direction = diffs.windows(2).all(|w| w[0].signum() == w[1].signum());
-    within_range && same_direction
-}
-
-fn part_1(input: &str) -> usize {
-    parse_input(input).filter(valid_report).count()
-}
-
-fn part_2(input: &str) -> usize {
-    parse_input(input)
-        .filter(|report| {
-            // Brute force check each permutation of the report without a level
-            (0..report.len()).any(|i| {
-                let mut copy = report.clone();
-                copy.remove(i);
-                valid_report(&copy)
-            })
+use crate::parse::{lines, ParseError};
+use crate::solution::{Answer, Example, Solution};
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    lines(input)
+        .filter(|line| !line.text.is_empty())
+        .map(|line| {
+            line.text
+                .split_whitespace()
+                .map(|str| line.parse(str))
+                .collect::<Result<Vec<i64>, ParseError>>()
         })
+        .collect()
+}
+
+/// How far apart adjacent levels may be in a safe report
+const STEPS: RangeInclusive<i64> = 1..=3;
+
+/// The indexes of the longest subsequence of `report` where each level is a step (in `steps`)
+/// above the last, i.e. the most levels that can be kept while staying safe and increasing
+fn longest_safe_run(report: &[i64], steps: &RangeInclusive<i64>) -> Vec<usize> {
+    // longest[i] is the length of the longest such subsequence ending with level i,
+    // and previous[i] the level before i in it
+    let mut longest: Vec<usize> = Vec::with_capacity(report.len());
+    let mut previous: Vec<Option<usize>> = Vec::with_capacity(report.len());
+    for (i, level) in report.iter().enumerate() {
+        let before = (0..i)
+            .filter(|j| steps.contains(&(level - report[*j])))
+            .max_by_key(|j| longest[*j]);
+        longest.push(before.map_or(0, |j| longest[j]) + 1);
+        previous.push(before);
+    }
+
+    let mut run = Vec::new();
+    let mut last = (0..report.len()).max_by_key(|i| longest[*i]);
+    while let Some(i) = last {
+        run.push(i);
+        last = previous[i];
+    }
+    run.reverse();
+    run
+}
+
+/// The indexes of the fewest levels that need removing from the report for it to be safe,
+/// meaning it's all increasing or all decreasing by a step in `steps`
+fn removals(report: &[i64], steps: &RangeInclusive<i64>) -> Vec<usize> {
+    let decreasing: Vec<i64> = report.iter().map(|level| -level).collect();
+    let increasing_run = longest_safe_run(report, steps);
+    let decreasing_run = longest_safe_run(&decreasing, steps);
+    let kept = if decreasing_run.len() > increasing_run.len() {
+        decreasing_run
+    } else {
+        increasing_run
+    };
+    (0..report.len())
+        .filter(|i| kept.binary_search(i).is_err())
+        .collect()
+}
+
+fn min_removals(report: &[i64], steps: &RangeInclusive<i64>) -> usize {
+    removals(report, steps).len()
+}
+
+/// Whether the report is safe after removing at most `tolerance` levels
+fn valid_report(report: &[i64], steps: &RangeInclusive<i64>, tolerance: usize) -> bool {
+    min_removals(report, steps) <= tolerance
+}
+
+fn count_valid(reports: &[Vec<i64>], steps: &RangeInclusive<i64>, tolerance: usize) -> usize {
+    reports
+        .iter()
+        .filter(|report| valid_report(report, steps, tolerance))
         .count()
 }
 
-pub fn run(input: &str) {
-    println!("part 1 solution: {}", part_1(input));
-    println!("part 2 solution: {}", part_2(input));
+fn part_1(reports: &[Vec<i64>]) -> usize {
+    count_valid(reports, &STEPS, 0)
+}
+
+fn part_2(reports: &[Vec<i64>]) -> usize {
+    // The problem dampener tolerates a single bad level
+    count_valid(reports, &STEPS, 1)
+}
+
+/// Why a report isn't safe, at the (0-based) indexes of the first offending pair of levels
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Failure {
+    /// The levels switch between increasing and decreasing
+    Direction(usize, usize),
+    /// The levels are too close together or too far apart
+    Step(usize, usize),
+}
+
+/// The first reason the report isn't safe, if there is one
+fn first_failure(report: &[i64], steps: &RangeInclusive<i64>) -> Option<Failure> {
+    let mut direction = 0;
+    for (i, pair) in report.windows(2).enumerate() {
+        let diff = pair[1] - pair[0];
+        if !steps.contains(&diff.abs()) {
+            return Some(Failure::Step(i, i + 1));
+        }
+        if direction == 0 {
+            direction = diff.signum();
+        } else if diff != 0 && diff.signum() != direction {
+            return Some(Failure::Direction(i, i + 1));
+        }
+    }
+    None
+}
+
+/// The diagnosis of a single report under the problem dampener
+#[derive(Debug, Eq, PartialEq, Clone)]
+struct Explanation {
+    report: Vec<i64>,
+    failure: Option<Failure>,
+    /// The indexes of the fewest levels whose removal makes the report safe
+    removals: Vec<usize>,
+}
+
+impl Explanation {
+    fn new(report: &[i64], steps: &RangeInclusive<i64>) -> Self {
+        Explanation {
+            report: report.to_vec(),
+            failure: first_failure(report, steps),
+            removals: removals(report, steps),
+        }
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.report.iter().join(" "))?;
+        let (i, j) = match self.failure {
+            None => return write!(f, "safe"),
+            Some(Failure::Direction(i, j)) => {
+                write!(f, "unsafe, changes direction")?;
+                (i, j)
+            }
+            Some(Failure::Step(i, j)) => {
+                write!(
+                    f,
+                    "unsafe, step of {}",
+                    (self.report[j] - self.report[i]).abs()
+                )?;
+                (i, j)
+            }
+        };
+        write!(
+            f,
+            " between indexes {} and {} ({} -> {}), ",
+            i, j, self.report[i], self.report[j]
+        )?;
+        let removed = self
+            .removals
+            .iter()
+            .map(|i| format!("{} ({})", i, self.report[*i]))
+            .join(", ");
+        match self.removals.len() {
+            1 => write!(f, "safe without 1 level: index {}", removed),
+            n => write!(f, "safe without {} levels: indexes {}", n, removed),
+        }
+    }
+}
+
+const EXAMPLE: &str = "\
+    7 6 4 2 1\n\
+    1 2 7 8 9\n\
+    9 7 6 2 1\n\
+    1 3 2 4 5\n\
+    8 6 4 4 1\n\
+    1 3 6 7 9";
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<i64>>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_1: Some("2"),
+        part_2: Some("4"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
+
+    fn explain(input: &Self::Input) -> Option<String> {
+        Some(
+            input
+                .iter()
+                .enumerate()
+                .map(|(i, report)| format!("{}. {}", i + 1, Explanation::new(report, &STEPS)))
+                .join("\n"),
+        )
+    }
+}
+
+crate::example_tests!(Day2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_removals() {
+        let reports = parse_input(EXAMPLE).unwrap();
+        assert_eq!(
+            vec![0, 2, 2, 1, 1, 0],
+            reports
+                .iter()
+                .map(|report| min_removals(report, &STEPS))
+                .collect::<Vec<_>>()
+        );
+        // Removing the first level is enough, even though it makes the report increasing
+        assert_eq!(1, min_removals(&[9, 1, 2, 3], &STEPS));
+        assert_eq!(0, min_removals(&[], &STEPS));
+        assert_eq!(0, min_removals(&[5], &STEPS));
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let reports = parse_input(EXAMPLE).unwrap();
+        assert_eq!(2, count_valid(&reports, &STEPS, 0));
+        assert_eq!(4, count_valid(&reports, &STEPS, 1));
+        assert_eq!(6, count_valid(&reports, &STEPS, 2));
+    }
+
+    #[test]
+    fn test_steps() {
+        let reports = parse_input(EXAMPLE).unwrap();
+        // 1 2 7 8 9 and 9 7 6 2 1 are safe if levels can differ by up to five
+        assert_eq!(4, count_valid(&reports, &(1..=5), 0));
+        // and 8 6 4 4 1 if they can stay the same
+        assert_eq!(5, count_valid(&reports, &(0..=5), 0));
+    }
+
+    #[test]
+    fn test_removals() {
+        assert_eq!(Vec::<usize>::new(), removals(&[7, 6, 4, 2, 1], &STEPS));
+        assert_eq!(vec![0, 1], removals(&[1, 2, 7, 8, 9], &STEPS));
+        assert_eq!(vec![3, 4], removals(&[9, 7, 6, 2, 1], &STEPS));
+        assert_eq!(vec![0], removals(&[9, 1, 2, 3], &STEPS));
+        assert_eq!(Vec::<usize>::new(), removals(&[], &STEPS));
+    }
+
+    #[test]
+    fn test_first_failure() {
+        assert_eq!(None, first_failure(&[7, 6, 4, 2, 1], &STEPS));
+        assert_eq!(
+            Some(Failure::Step(1, 2)),
+            first_failure(&[1, 2, 7, 8, 9], &STEPS)
+        );
+        assert_eq!(
+            Some(Failure::Direction(1, 2)),
+            first_failure(&[1, 3, 2, 4, 5], &STEPS)
+        );
+        assert_eq!(
+            Some(Failure::Step(2, 3)),
+            first_failure(&[8, 6, 4, 4, 1], &STEPS)
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(
+            Some(
+                "\
+                1. 7 6 4 2 1: safe\n\
+                2. 1 2 7 8 9: unsafe, step of 5 between indexes 1 and 2 (2 -> 7), \
+                safe without 2 levels: indexes 0 (1), 1 (2)\n\
+                3. 9 7 6 2 1: unsafe, step of 4 between indexes 2 and 3 (6 -> 2), \
+                safe without 2 levels: indexes 3 (2), 4 (1)\n\
+                4. 1 3 2 4 5: unsafe, changes direction between indexes 1 and 2 (3 -> 2), \
+                safe without 1 level: index 1 (3)\n\
+                5. 8 6 4 4 1: unsafe, step of 0 between indexes 2 and 3 (4 -> 4), \
+                safe without 1 level: index 2 (4)\n\
+                6. 1 3 6 7 9: safe"
+                    .to_string()
+            ),
+            Day2::explain(&parse_input(EXAMPLE).unwrap())
+        );
+    }
 }
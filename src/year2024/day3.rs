@@ -1,47 +1,127 @@
 //! https://adventofcode.com/2024/day/3
 
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::collections::HashSet;
-use std::ops::{Not, Range};
+use crate::interpreter::{disable, enable, mul, Interpreter, DO, DONT, MUL};
+use crate::parse::ParseError;
+use crate::solution::{Answer, Example, Solution};
+use itertools::Itertools;
 
-lazy_static! {
-    static ref MUL_REGEX: Regex = Regex::new(r"mul\((\d\d?\d?),(\d\d?\d?)\)").unwrap();
+/// An interpreter for `mul` instructions, and optionally `do` and `don't`
+fn interpreter(conditionals: bool) -> Interpreter {
+    let mut interpreter = Interpreter::new();
+    interpreter.register(MUL, mul);
+    if conditionals {
+        interpreter.register(DO, enable);
+        interpreter.register(DONT, disable);
+    }
+    interpreter
 }
 
 fn part_1(input: &str) -> i64 {
-    MUL_REGEX
-        .captures_iter(input)
-        .map(|g| g.extract())
-        .map(|(_, [left, right])| (left.parse::<i64>().unwrap(), right.parse::<i64>().unwrap()))
-        .map(|(left, right)| left * right)
-        .sum()
+    interpreter(false).run(input).state.total
 }
 
 fn part_2(input: &str) -> i64 {
-    // Construct a set of ranges between `don't()` and `do()` where matches should be ignored
-    let disabled_regex = Regex::new(r"(don't\(\))[\s\S]+?(do\(\))").unwrap();
-    let disabled_ranges: HashSet<Range<usize>> = HashSet::from_iter(
-        disabled_regex
-            .captures_iter(input)
-            .map(|g| g.get(0).unwrap().range()),
-    );
-    MUL_REGEX
-        .captures_iter(input)
-        .filter(|g| {
-            let pos = &g.get(0).unwrap().start();
-            disabled_ranges
+    interpreter(true).run(input).state.total
+}
+
+const EXAMPLE_1: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+const EXAMPLE_2: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = String;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE_1,
+            part_1: Some("161"),
+            part_2: None,
+        },
+        Example {
+            input: EXAMPLE_2,
+            part_1: None,
+            part_2: Some("48"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_string())
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
+
+    fn explain(input: &Self::Input) -> Option<String> {
+        Some(
+            interpreter(true)
+                .run(input)
+                .trace
                 .iter()
-                .any(|range| range.contains(pos))
-                .not()
-        })
-        .map(|g| g.extract())
-        .map(|(_, [left, right])| (left.parse::<i64>().unwrap(), right.parse::<i64>().unwrap()))
-        .map(|(left, right)| left * right)
-        .sum()
+                .map(|step| format!("{}: {} {}", step.token.offset, step.token, step.status))
+                .join("\n"),
+        )
+    }
 }
 
-pub fn run(input: &str) {
-    println!("part 1 solution: {}", part_1(input));
-    println!("part 2 solution: {}", part_2(input));
+crate::example_tests!(Day3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            vec![
+                "mul(2,4)",
+                "don't()",
+                "mul(5,5)",
+                "mul(11,8)",
+                "do()",
+                "mul(8,5)"
+            ],
+            interpreter(true)
+                .tokenize(EXAMPLE_2)
+                .iter()
+                .map(|token| token.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(
+            Some(
+                "\
+                1: mul(2,4) executed\n\
+                20: don't() executed\n\
+                28: mul(5,5) skipped\n\
+                48: mul(11,8) skipped\n\
+                59: do() executed\n\
+                64: mul(8,5) executed"
+                    .to_string()
+            ),
+            Day3::explain(&EXAMPLE_2.to_string())
+        );
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(161, part_1(EXAMPLE_1));
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(48, part_2(EXAMPLE_2));
+        // A trailing don't() disables everything after it
+        assert_eq!(8, part_2("mul(2,4)don't()mul(5,5)mul(11,8)"));
+        assert_eq!(33, part_2("don't()mul(2,4)do()mul(3,11)don't()mul(5,5)"));
+    }
 }
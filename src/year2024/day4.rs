@@ -1,108 +1,78 @@
 //! https://adventofcode.com/2024/day/4
 
-const CARDINAL_DIRECTIONS: [(i32, i32); 4] = [
-    (0, -1), // N
-    (1, 0),  // E
-    (0, 1),  // S
-    (-1, 0), // W
-];
-
-const INTERCARDINAL_DIRECTIONS: [(i32, i32); 4] = [
-    (1, -1),  // NE
-    (1, 1),   // SE
-    (-1, 1),  // SW
-    (-1, -1), // NW
-];
-
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.chars().collect())
-        .collect::<Vec<Vec<char>>>()
-}
+use crate::grid::{Grid, ALL_DIRECTIONS};
+use crate::parse::ParseError;
+use crate::pattern::Pattern;
+use crate::solution::{Answer, Example, Solution};
+use crate::word_search::WordSearch;
 
-fn check_direction(
-    search_string: &str,
-    grid: &Vec<Vec<char>>,
-    mut x: i32,
-    mut y: i32,
-    dx: i32,
-    dy: i32,
-) -> bool {
-    let mut i: usize = 0;
-    while let Some(c) = grid
-        .get(y as usize)
-        .map(|row| row.get(x as usize))
-        .flatten()
-    {
-        if *c as u8 != *search_string.as_bytes().get(i).unwrap() {
-            return false;
-        }
-        x += dx;
-        y += dy;
-        i += 1;
-        if i == search_string.len() {
-            return true;
-        }
-    }
-    false
+fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(input, |_, c| Some(c))
 }
 
-fn part_1(input: &str) -> usize {
-    let grid = parse_input(input);
-    grid.iter()
-        .enumerate()
-        .map(|(y, line)| {
-            line.iter()
-                .enumerate()
-                // Look for the start of XMAS
-                .filter_map(|(x, c)| if *c == 'X' { Some(x) } else { None })
-                // Look for XMAS in all 8 directions
-                .map(|x| {
-                    CARDINAL_DIRECTIONS
-                        .iter()
-                        .chain(INTERCARDINAL_DIRECTIONS.iter())
-                        .map(|(dx, dy)| {
-                            check_direction("XMAS", &grid, x as i32, y as i32, *dx, *dy) as usize
-                        })
-                        .sum::<usize>()
-                })
-                .sum::<usize>()
-        })
-        .sum()
+fn part_1(grid: &Grid<char>) -> usize {
+    WordSearch::new(&ALL_DIRECTIONS).find(grid, &["XMAS"]).len()
 }
 
-fn part_2(input: &str) -> usize {
-    let grid = parse_input(input);
-    grid.iter()
-        .enumerate()
-        .map(|(y, line)| {
-            line.iter()
-                .enumerate()
-                // Look for the middle of MAS
-                .filter_map(|(x, c)| if *c == 'A' { Some(x) } else { None })
-                // Look for MAS (with an offset) in the intercardinal directions,
-                // only accept if both directions match
-                .filter(|x| {
-                    INTERCARDINAL_DIRECTIONS
-                        .iter()
-                        .filter(|(dx, dy)| {
-                            check_direction("MAS", &grid, *x as i32 - dx, y as i32 - dy, *dx, *dy)
-                        })
-                        .count()
-                        == 2
-                })
-                .count()
-        })
+fn part_2(grid: &Grid<char>) -> usize {
+    // Two MAS in the shape of an X, either way round
+    let x_mas = Pattern::parse(
+        "\
+        M.S\n\
+        .A.\n\
+        M.S",
+    )
+    .unwrap();
+    x_mas
+        .variants(true, true)
+        .iter()
+        .map(|pattern| pattern.find(grid).len())
         .sum()
 }
 
-pub fn run(input: &str) {
-    println!("part 1 solution: {}", part_1(input));
-    println!("part 2 solution: {}", part_2(input));
+const EXAMPLE: &str = "\
+    MMMSXXMASM\n\
+    MSAMXMSMSA\n\
+    AMXSXMAAMM\n\
+    MSAMASMSMX\n\
+    XMASAMXAMM\n\
+    XXAMMXXAMA\n\
+    SMSMSASXSS\n\
+    SAXAMASAAA\n\
+    MAMMMXMMMM\n\
+    MXMXAXMASX";
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Grid<char>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_1: Some("18"),
+        part_2: Some("9"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
+
+    fn explain(input: &Self::Input) -> Option<String> {
+        let search = WordSearch::new(&ALL_DIRECTIONS);
+        Some(search.render(input, &search.find(input, &["XMAS"]), '.'))
+    }
 }
 
+crate::example_tests!(Day4);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,27 +82,34 @@ mod tests {
         assert_eq!(
             4,
             part_1(
-                "\
+                &parse_input(
+                    "\
             XMAS\n\
             M..A\n\
             A..M\n\
             SAMX"
+                )
+                .unwrap()
             )
         );
         assert_eq!(
             2,
             part_1(
-                "\
+                &parse_input(
+                    "\
             X..S\n\
             .MA.\n\
             .MA.\n\
             X..S"
+                )
+                .unwrap()
             )
         );
         assert_eq!(
             2,
             part_1(
-                "\
+                &parse_input(
+                    "\
             XMASAMX\n\
             .......\n\
             .......\n\
@@ -140,6 +117,37 @@ mod tests {
             .......\n\
             .......\n\
             ......."
+                )
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_multi_byte() {
+        // The low bytes of Ř, ō, Ł and œ are X, M, A and S
+        assert_eq!(0, part_1(&parse_input("ŘōŁœ").unwrap()));
+        assert_eq!(
+            1,
+            part_1(
+                &parse_input(
+                    "\
+            ŘXMASœ\n\
+            ŘōŁœ☃☃"
+                )
+                .unwrap()
+            )
+        );
+        assert_eq!(
+            1,
+            part_2(
+                &parse_input(
+                    "\
+            MéS\n\
+            ŁAœ\n\
+            MüS"
+                )
+                .unwrap()
             )
         );
     }
@@ -149,16 +157,20 @@ mod tests {
         assert_eq!(
             1,
             part_2(
-                "\
+                &parse_input(
+                    "\
             M.S\n\
             .A.\n\
             M.S"
+                )
+                .unwrap()
             )
         );
         assert_eq!(
             9,
             part_2(
-                "\
+                &parse_input(
+                    "\
             .M.S......\n\
             ..A..MSMS.\n\
             .M.S.MAA..\n\
@@ -169,6 +181,8 @@ mod tests {
             .A.A.A.A..\n\
             M.M.M.M.M.\n\
             .........."
+                )
+                .unwrap()
             )
         );
     }
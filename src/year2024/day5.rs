@@ -1,139 +1,280 @@
 //! https://adventofcode.com/2024/day/5
 
+use crate::parse::{lines, Line, ParseError};
+use crate::solution::{Answer, Example, Solution};
 use itertools::Itertools;
-use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 
-fn parse_input(input: &str) -> (Vec<(u32, u32)>, Vec<Vec<u32>>) {
-    let lines: Vec<&str> = input.lines().collect();
-    let (rule_strs, update_strs) = lines.split(|line| line.is_empty()).collect_tuple().unwrap();
+/// A pair of pages, where the first must be printed before the second
+type Rule = (u32, u32);
 
-    let rules: Vec<(u32, u32)> = rule_strs
+type Update = Vec<u32>;
+
+/// An update as it was printed, and its pages in the order the rules require
+type OrderedUpdate = (Update, Update);
+
+fn parse_rule(line: &Line) -> Result<Rule, ParseError> {
+    let (before, after) = line
+        .text
+        .split('|')
+        .collect_tuple()
+        .ok_or_else(|| line.error(line.text, "expected a rule like `47|53`"))?;
+    Ok((line.parse(before)?, line.parse(after)?))
+}
+
+fn parse_update(line: &Line) -> Result<Update, ParseError> {
+    line.text.split(',').map(|n| line.parse(n)).collect()
+}
+
+fn parse_input(input: &str) -> Result<Vec<OrderedUpdate>, ParseError> {
+    let lines: Vec<Line> = lines(input).collect();
+    // The rules and updates are separated by a blank line
+    let separator = lines
         .iter()
-        .map(|rule_str| rule_str.split("|").collect_tuple().unwrap())
-        .map(|(l, r)| (l.parse().unwrap(), r.parse().unwrap()))
-        .collect();
+        .position(|line| line.text.is_empty())
+        .ok_or_else(|| {
+            ParseError::new(
+                lines.len() + 1,
+                1,
+                "",
+                "expected a blank line between the rules and the updates",
+            )
+        })?;
+    let (rule_lines, update_lines) = lines.split_at(separator);
 
-    let updates: Vec<Vec<u32>> = update_strs
+    let rules: Vec<Rule> = rule_lines
         .iter()
-        .map(|update_str| update_str.split(",").map(|n| n.parse().unwrap()).collect())
-        .collect();
+        .map(parse_rule)
+        .collect::<Result<_, _>>()?;
+    let rule_map = construct_rule_map(&rules);
 
-    (rules, updates)
+    update_lines
+        .iter()
+        .filter(|line| !line.text.is_empty())
+        .map(|line| {
+            let update = parse_update(line)?;
+            match topological_order(&update, &rule_map) {
+                Ok(ordered) => Ok((update, ordered)),
+                Err(cycle) => Err(line.error(line.text, cycle.to_string())),
+            }
+        })
+        .collect()
 }
 
-/// Create a map of page -> pages that cannot be before that page
-fn construct_rule_map(rules: &Vec<(u32, u32)>) -> HashMap<&u32, HashSet<&u32>> {
+/// Map of page -> pages that must be printed before that page
+type RuleMap = HashMap<u32, HashSet<u32>>;
+
+fn construct_rule_map(rules: &[Rule]) -> RuleMap {
     rules
         .iter()
         .fold(HashMap::new(), |mut acc, (before, after)| {
-            acc.entry(after)
-                .or_insert_with(HashSet::default)
-                .insert(before);
+            acc.entry(*after).or_default().insert(*before);
             acc
         })
 }
 
-fn cmp_by_rules(left: &u32, right: &u32, rules: &HashMap<&u32, HashSet<&u32>>) -> Ordering {
-    if rules
-        .get(right)
-        .map_or(false, |must_before| must_before.contains(left))
-    {
-        Ordering::Less
-    } else if rules
-        .get(left)
-        .map_or(false, |must_before| must_before.contains(right))
-    {
-        Ordering::Greater
-    } else {
-        Ordering::Equal
+/// Pages whose rules require each one to be printed before the next, and the last before the first
+#[derive(Debug, Eq, PartialEq, Clone)]
+struct Cycle(Vec<u32>);
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Cycle(pages) = self;
+        write!(
+            f,
+            "the rules for pages {} contain a cycle ({})",
+            pages.iter().join(", "),
+            pages
+                .iter()
+                .zip(pages.iter().cycle().skip(1))
+                .map(|(before, after)| format!("{}|{}", before, after))
+                .join(", ")
+        )
     }
 }
 
-fn part_1(input: &str) -> usize {
-    let (rules, updates) = parse_input(input);
-    let rule_map = construct_rule_map(&rules);
+/// Find a cycle among pages that all have to wait for another of the pages
+fn find_cycle(pages: &[u32], rule_map: &RuleMap) -> Cycle {
+    // Walk backwards from page to page that must be printed before it, until one repeats
+    let mut path = vec![pages[0]];
+    loop {
+        let before = rule_map[path.last().unwrap()]
+            .iter()
+            .filter(|page| pages.contains(page))
+            .min()
+            .copied()
+            .unwrap();
+        if let Some(start) = path.iter().position(|page| *page == before) {
+            let mut cycle = path.split_off(start);
+            cycle.reverse();
+            // Start from the lowest page, so the same cycle is always reported the same way
+            let lowest = cycle.iter().position_min().unwrap();
+            cycle.rotate_left(lowest);
+            return Cycle(cycle);
+        }
+        path.push(before);
+    }
+}
+
+/// Order the pages of an update so that every rule between them is followed.
+/// Pages that the rules don't order keep their relative order in the update
+fn topological_order(update: &[u32], rule_map: &RuleMap) -> Result<Update, Cycle> {
+    let mut remaining = update.to_vec();
+    let mut ordered = Vec::with_capacity(update.len());
+    while !remaining.is_empty() {
+        // The first page that isn't waiting for any of the remaining pages
+        let next = remaining.iter().position(|page| {
+            rule_map
+                .get(page)
+                .is_none_or(|before| !remaining.iter().any(|other| before.contains(other)))
+        });
+        match next {
+            Some(i) => ordered.push(remaining.remove(i)),
+            None => return Err(find_cycle(&remaining, rule_map)),
+        }
+    }
+    Ok(ordered)
+}
+
+fn middle_page(update: &[u32]) -> usize {
+    update[update.len() / 2] as usize
+}
 
+fn part_1(updates: &[OrderedUpdate]) -> usize {
     updates
         .iter()
-        .filter(|update| {
-            update
-                .iter()
-                .sorted_by(|left, right| cmp_by_rules(left, right, &rule_map))
-                .eq(update.iter())
-        })
-        .map(|update| *update.get(update.len() / 2).unwrap() as usize)
+        .filter(|(update, ordered)| ordered == update)
+        .map(|(update, _)| middle_page(update))
         .sum()
 }
 
-fn part_2(input: &str) -> usize {
-    let (rules, updates) = parse_input(input);
-    let rule_map = construct_rule_map(&rules);
-
+fn part_2(updates: &[OrderedUpdate]) -> usize {
     updates
         .iter()
-        .filter_map(|update| {
-            let update_sorted: Vec<u32> = update
-                .iter()
-                .sorted_by(|left, right| cmp_by_rules(left, right, &rule_map))
-                .copied()
-                .collect();
-            if !update_sorted.eq(update) {
-                Some(update_sorted)
-            } else {
-                None
-            }
-        })
-        .map(|update| *update.get(update.len() / 2).unwrap() as usize)
+        .filter(|(update, ordered)| ordered != update)
+        .map(|(_, ordered)| middle_page(ordered))
         .sum()
 }
 
-pub fn run(input: &str) {
-    println!("part 1 solution: {}", part_1(input));
-    println!("part 2 solution: {}", part_2(input));
+const EXAMPLE: &str = "\
+    47|53\n\
+    97|13\n\
+    97|61\n\
+    97|47\n\
+    75|29\n\
+    61|13\n\
+    75|53\n\
+    29|13\n\
+    97|29\n\
+    53|29\n\
+    61|53\n\
+    97|53\n\
+    61|29\n\
+    47|13\n\
+    75|47\n\
+    97|75\n\
+    47|61\n\
+    75|61\n\
+    47|29\n\
+    75|13\n\
+    53|13\n\
+\n\
+    75,47,61,53,29\n\
+    97,61,53,29,13\n\
+    75,29,13\n\
+    75,97,47,61,53\n\
+    61,13,29\n\
+    97,13,75,29,47";
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<OrderedUpdate>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_1: Some("143"),
+        part_2: Some("123"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
 }
 
+crate::example_tests!(Day5);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = "\
-        47|53\n\
-        97|13\n\
-        97|61\n\
-        97|47\n\
-        75|29\n\
-        61|13\n\
-        75|53\n\
-        29|13\n\
-        97|29\n\
-        53|29\n\
-        61|53\n\
-        97|53\n\
-        61|29\n\
-        47|13\n\
-        75|47\n\
-        97|75\n\
-        47|61\n\
-        75|61\n\
-        47|29\n\
-        75|13\n\
-        53|13\n\
-\n\
-        75,47,61,53,29\n\
-        97,61,53,29,13\n\
-        75,29,13\n\
-        75,97,47,61,53\n\
-        61,13,29\n\
-        97,13,75,29,47";
+    #[test]
+    fn test_parse_input_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 4, "1x", "invalid digit found in string")),
+            parse_input("47|53\n97|1x\n\n75,47")
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                3,
+                1,
+                "",
+                "expected a blank line between the rules and the updates"
+            )),
+            parse_input("47|53\n75,47")
+        );
+    }
 
     #[test]
-    fn test_part_1() {
-        assert_eq!(143, part_1(EXAMPLE))
+    fn test_topological_order() {
+        assert_eq!(
+            vec![
+                vec![75, 47, 61, 53, 29],
+                vec![97, 61, 53, 29, 13],
+                vec![75, 29, 13],
+                vec![97, 75, 47, 61, 53],
+                vec![61, 29, 13],
+                vec![97, 75, 47, 29, 13],
+            ],
+            parse_input(EXAMPLE)
+                .unwrap()
+                .into_iter()
+                .map(|(_, ordered)| ordered)
+                .collect::<Vec<_>>()
+        );
+        // Pages without rules between them keep their order
+        assert_eq!(
+            Ok(vec![5, 2, 1, 4]),
+            topological_order(&[5, 4, 2, 1], &construct_rule_map(&[(2, 4), (1, 4)]))
+        );
     }
 
     #[test]
-    fn test_part_2() {
-        assert_eq!(123, part_2(EXAMPLE))
+    fn test_cycle() {
+        let rule_map = construct_rule_map(&[(1, 2), (2, 3), (3, 1), (4, 1)]);
+        assert_eq!(
+            Err(Cycle(vec![1, 2, 3])),
+            topological_order(&[4, 3, 2, 1], &rule_map)
+        );
+        // Only the rules between the update's pages matter
+        assert_eq!(Ok(vec![4, 1, 2]), topological_order(&[4, 2, 1], &rule_map));
+        assert_eq!(
+            Err(ParseError::new(
+                4,
+                1,
+                "13,75,47",
+                "the rules for pages 47, 75 contain a cycle (47|75, 75|47)"
+            )),
+            parse_input("75|47\n47|75\n\n13,75,47")
+        );
     }
 }
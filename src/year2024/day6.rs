@@ -1,9 +1,10 @@
 //! https://adventofcode.com/2024/day/6
 
+use crate::grid::{Coordinate, Grid};
+use crate::parse::ParseError;
+use crate::solution::{Answer, Example, Solution};
 use std::collections::HashSet;
 
-type Coordinate = (i32, i32);
-
 #[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
 enum CardinalDirections {
     North,
@@ -40,39 +41,27 @@ impl CardinalDirections {
     }
 }
 
-fn parse_input(input: &str) -> (Coordinate, Vec<Vec<bool>>) {
-    let guard_pos: Coordinate = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .enumerate()
-        .flat_map(|(y, line)| line.chars().enumerate().map(move |(x, c)| ((x, y), c)))
-        .find_map(|((x, y), c)| match c {
-            '^' => Some((x as i32, y as i32)),
-            _ => None,
-        })
-        .unwrap();
-    let obstacles = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.chars().map(|c| c == '#').collect())
-        .collect();
-    (guard_pos, obstacles)
-}
-
-fn get_obstacle<'a>(obstacles: &'a Vec<Vec<bool>>, (x, y): &Coordinate) -> Option<&'a bool> {
-    obstacles
-        .get(*y as usize)
-        .map(|row| row.get(*x as usize))
-        .flatten()
+fn parse_input(input: &str) -> Result<(Coordinate, Grid<bool>), ParseError> {
+    let mut guard_pos = None;
+    let obstacles = Grid::parse(input, |pos, c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        '^' => {
+            guard_pos = Some(pos);
+            Some(false)
+        }
+        _ => None,
+    })?;
+    let guard_pos = guard_pos.ok_or_else(|| ParseError::new(1, 1, "", "expected a guard (`^`)"))?;
+    Ok((guard_pos, obstacles))
 }
 
-fn is_loop((mut guard_x, mut guard_y): &Coordinate, obstacles: &Vec<Vec<bool>>) -> bool {
+fn is_loop((mut guard_x, mut guard_y): &Coordinate, obstacles: &Grid<bool>) -> bool {
     let mut direction = CardinalDirections::North;
     let mut visited = HashSet::new();
-    while let Some(next_space_occupied) = get_obstacle(
-        &obstacles,
-        &(guard_x + direction.dx(), guard_y + direction.dy()),
-    ) {
+    while let Some(next_space_occupied) =
+        obstacles.get((guard_x + direction.dx(), guard_y + direction.dy()))
+    {
         if *next_space_occupied {
             direction = direction.turn_right();
         } else if visited.contains(&((guard_x, guard_y), direction)) {
@@ -86,14 +75,14 @@ fn is_loop((mut guard_x, mut guard_y): &Coordinate, obstacles: &Vec<Vec<bool>>)
     false
 }
 
-fn part_1(input: &str) -> HashSet<Coordinate> {
-    let ((mut guard_x, mut guard_y), obstacles) = parse_input(input);
+fn part_1(
+    ((mut guard_x, mut guard_y), obstacles): &(Coordinate, Grid<bool>),
+) -> HashSet<Coordinate> {
     let mut direction = CardinalDirections::North;
     let mut visited = HashSet::new();
-    while let Some(next_space_occupied) = get_obstacle(
-        &obstacles,
-        &(guard_x + direction.dx(), guard_y + direction.dy()),
-    ) {
+    while let Some(next_space_occupied) =
+        obstacles.get((guard_x + direction.dx(), guard_y + direction.dy()))
+    {
         if *next_space_occupied {
             direction = direction.turn_right();
         } else {
@@ -110,98 +99,116 @@ fn part_1(input: &str) -> HashSet<Coordinate> {
 /// so this just looks at every space that is visited
 /// and sees if placing an obstacle causes a loop
 /// (brute force basically)
-fn part_2(input: &str) -> HashSet<Coordinate> {
+fn part_2(input: &(Coordinate, Grid<bool>)) -> HashSet<Coordinate> {
     let visited = part_1(input);
 
-    let (guard_pos, obstacles) = parse_input(input);
+    let (guard_pos, obstacles) = input;
     obstacles
         .iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .map(move |(x, has_obstacle)| ((x as i32, y as i32), has_obstacle))
-                .filter(|(pos, has_obstacle)| {
-                    !*has_obstacle
-                        && *pos != guard_pos
-                        && *pos != (guard_pos.0, guard_pos.1 - 1)
-                        && visited.contains(pos)
-                })
-                .map(|(pos, _)| pos)
+        .filter(|(pos, has_obstacle)| {
+            !*has_obstacle
+                && pos != guard_pos
+                && *pos != (guard_pos.0, guard_pos.1 - 1)
+                && visited.contains(pos)
         })
-        .filter(|(x, y)| {
+        .map(|(pos, _)| pos)
+        .filter(|pos| {
             let mut copy = obstacles.clone();
-            copy[*y as usize][*x as usize] = true;
-            is_loop(&guard_pos, &copy)
+            copy[*pos] = true;
+            is_loop(guard_pos, &copy)
         })
         .collect()
 }
 
-pub fn run(input: &str) {
-    println!("part 1 solution: {}", part_1(input).len());
-    println!("part 2 solution: {}", part_2(input).len());
+const EXAMPLE: &str = "\
+    ....#.....\n\
+    .........#\n\
+    ..........\n\
+    ..#.......\n\
+    .......#..\n\
+    ..........\n\
+    .#..^.....\n\
+    ........#.\n\
+    #.........\n\
+    ......#...";
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = (Coordinate, Grid<bool>);
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_1: Some("41"),
+        part_2: Some("6"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).len().into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).len().into()
+    }
 }
 
+crate::example_tests!(Day6);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = "\
-        ....#.....\n\
-        .........#\n\
-        ..........\n\
-        ..#.......\n\
-        .......#..\n\
-        ..........\n\
-        .#..^.....\n\
-        ........#.\n\
-        #.........\n\
-        ......#...";
-
-    #[test]
-    fn test_part_1() {
-        assert_eq!(41, part_1(EXAMPLE).len());
-    }
-
     #[test]
     fn test_part_2() {
-        assert_eq!(6, part_2(EXAMPLE).len());
         assert_eq!(
             2,
             part_2(
-                "\
+                &parse_input(
+                    "\
                 ..##..\n\
                 .....#\n\
                 ..^...\n\
                 ......\n\
                 ....#.\n\
                 "
+                )
+                .unwrap()
             )
             .len()
         );
         assert_eq!(
             1,
             part_2(
-                "\
+                &parse_input(
+                    "\
                 ..#...\n\
                 .....#\n\
                 ..^...\n\
                 ...#..\n\
                 ....#.\n\
                 "
+                )
+                .unwrap()
             )
             .len()
         );
         assert_eq!(
             1,
             part_2(
-                "\
+                &parse_input(
+                    "\
                 ..#...\n\
                 .....#\n\
                 ..^..#\n\
                 ...#..\n\
                 ....#.\n\
                 "
+                )
+                .unwrap()
             )
             .len()
         );
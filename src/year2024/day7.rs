@@ -1,5 +1,7 @@
 //! https://adventofcode.com/2024/day/7
 
+use crate::parse::{lines, ParseError};
+use crate::solution::{Answer, Example, Solution};
 use itertools::Itertools;
 use std::rc::Rc;
 
@@ -23,16 +25,21 @@ impl Symbol {
     }
 }
 
-fn parse_input(input: &str) -> Vec<(u64, Vec<u64>)> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.split(": ").collect_tuple().unwrap())
-        .map(|(solution, operands)| {
-            (
-                solution.parse().unwrap(),
-                operands.split(" ").map(|s| s.parse().unwrap()).collect(),
-            )
+fn parse_input(input: &str) -> Result<Vec<(u64, Vec<u64>)>, ParseError> {
+    lines(input)
+        .filter(|line| !line.text.is_empty())
+        .map(|line| {
+            let (solution, operands) =
+                line.text.split(": ").collect_tuple().ok_or_else(|| {
+                    line.error(line.text, "expected an equation like `190: 10 19`")
+                })?;
+            Ok((
+                line.parse(solution)?,
+                operands
+                    .split(" ")
+                    .map(|s| line.parse(s))
+                    .collect::<Result<_, _>>()?,
+            ))
         })
         .collect()
 }
@@ -81,28 +88,22 @@ fn solve(
                 concat,
             ) {
                 Some(e)
+            } else if concat {
+                solve(
+                    solution,
+                    Rc::from(Symbol::Concatenate(symbol.clone(), c)),
+                    &ns[1..],
+                    true,
+                )
             } else {
-                if concat {
-                    if let Some(e) = solve(
-                        solution,
-                        Rc::from(Symbol::Concatenate(symbol.clone(), c)),
-                        &ns[1..],
-                        true,
-                    ) {
-                        Some(e)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
+                None
             }
         }
     }
 }
 
-fn solve_calibrations(input: &str, concat: bool) -> u64 {
-    parse_input(input)
+fn solve_calibrations(calibrations: &[(u64, Vec<u64>)], concat: bool) -> u64 {
+    calibrations
         .iter()
         .filter_map(|(solution, values)| {
             solve(
@@ -116,33 +117,39 @@ fn solve_calibrations(input: &str, concat: bool) -> u64 {
         .sum()
 }
 
-pub fn run(input: &str) {
-    println!("part 1 solution: {}", solve_calibrations(input, false));
-    println!("part 2 solution: {}", solve_calibrations(input, true));
-}
+const EXAMPLE: &str = "\
+    190: 10 19\n\
+    3267: 81 40 27\n\
+    83: 17 5\n\
+    156: 15 6\n\
+    7290: 6 8 6 15\n\
+    161011: 16 10 13\n\
+    192: 17 8 14\n\
+    21037: 9 7 18 13\n\
+    292: 11 6 16 20";
+
+pub struct Day7;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for Day7 {
+    type Input = Vec<(u64, Vec<u64>)>;
 
-    const EXAMPLE: &str = "\
-        190: 10 19\n\
-        3267: 81 40 27\n\
-        83: 17 5\n\
-        156: 15 6\n\
-        7290: 6 8 6 15\n\
-        161011: 16 10 13\n\
-        192: 17 8 14\n\
-        21037: 9 7 18 13\n\
-        292: 11 6 16 20";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_1: Some("3749"),
+        part_2: Some("11387"),
+    }];
 
-    #[test]
-    fn test_part_1() {
-        assert_eq!(3749, solve_calibrations(EXAMPLE, false));
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    #[test]
-    fn test_part_2() {
-        assert_eq!(11387, solve_calibrations(EXAMPLE, true));
+    fn part_1(input: &Self::Input) -> Answer {
+        solve_calibrations(input, false).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        solve_calibrations(input, true).into()
     }
 }
+
+crate::example_tests!(Day7);
@@ -1,30 +1,24 @@
 //! https://adventofcode.com/2024/day/8
 
+use crate::grid::{Coordinate, Grid};
+use crate::parse::ParseError;
+use crate::solution::{Answer, Example, Solution};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
-type Coordinate = (i32, i32);
+/// Map of frequency -> antenna locations
+type AntennaMap = HashMap<char, Vec<Coordinate>>;
 
-fn parse_input(input: &str) -> (HashMap<char, Vec<Coordinate>>, usize) {
-    let grid_size = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.chars().filter(|c| !c.is_whitespace()).count())
-        .next()
-        .unwrap();
+fn parse_input(input: &str) -> Result<(AntennaMap, Grid<char>), ParseError> {
+    let grid = Grid::parse(input, |_, c| Some(c))?;
 
-    let antenna_map = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.char_indices()
-                .filter(|(_, c)| c.is_alphanumeric())
-                .map(move |(x, c)| (c, (x as i32, y as i32)))
-        })
+    let antenna_map = grid
+        .iter()
+        .filter(|(_, c)| c.is_alphanumeric())
+        .map(|(pos, c)| (*c, pos))
         .into_group_map();
 
-    (antenna_map, grid_size)
+    Ok((antenna_map, grid))
 }
 
 struct AntinodeIterator {
@@ -55,16 +49,11 @@ fn calculate_antinodes(
 }
 
 fn calculate_antinode(left: &Coordinate, right: &Coordinate) -> Coordinate {
-    calculate_antinodes(left, right).skip(1).next().unwrap()
-}
-
-fn is_valid((x, y): Coordinate, grid_size: usize) -> bool {
-    x >= 0 && x < grid_size as i32 && y >= 0 && y < grid_size as i32
+    calculate_antinodes(left, right).nth(1).unwrap()
 }
 
-fn part_1(input: &str) -> usize {
+fn part_1((antenna_map, grid): &(AntennaMap, Grid<char>)) -> usize {
     let mut antinode_locations = HashSet::new();
-    let (antenna_map, grid_size) = parse_input(input);
 
     antenna_map.values().for_each(|antennas| {
         antennas
@@ -76,10 +65,10 @@ fn part_1(input: &str) -> usize {
                     calculate_antinode(left, right),
                     calculate_antinode(right, left),
                 );
-                if is_valid(antinode_1, grid_size) {
+                if grid.contains(antinode_1) {
                     antinode_locations.insert(antinode_1);
                 }
-                if is_valid(antinode_2, grid_size) {
+                if grid.contains(antinode_2) {
                     antinode_locations.insert(antinode_2);
                 }
             })
@@ -88,9 +77,8 @@ fn part_1(input: &str) -> usize {
     antinode_locations.len()
 }
 
-fn part_2(input: &str) -> usize {
+fn part_2((antenna_map, grid): &(AntennaMap, Grid<char>)) -> usize {
     let mut antinode_locations = HashSet::new();
-    let (antenna_map, grid_size) = parse_input(input);
 
     antenna_map.values().for_each(|antennas| {
         antennas
@@ -99,12 +87,12 @@ fn part_2(input: &str) -> usize {
             .filter(|(left, right)| left != right)
             .for_each(|(left, right)| {
                 calculate_antinodes(left, right)
-                    .take_while(|pos| is_valid(*pos, grid_size))
+                    .take_while(|pos| grid.contains(*pos))
                     .for_each(|pos| {
                         antinode_locations.insert(pos);
                     });
                 calculate_antinodes(right, left)
-                    .take_while(|pos| is_valid(*pos, grid_size))
+                    .take_while(|pos| grid.contains(*pos))
                     .for_each(|pos| {
                         antinode_locations.insert(pos);
                     });
@@ -114,29 +102,50 @@ fn part_2(input: &str) -> usize {
     antinode_locations.len()
 }
 
-pub fn run(input: &str) {
-    println!("part 1 solution: {}", part_1(input));
-    println!("part 2 solution: {}", part_2(input));
+const EXAMPLE: &str = "\
+    ............\n\
+    ........0...\n\
+    .....0......\n\
+    .......0....\n\
+    ....0.......\n\
+    ......A.....\n\
+    ............\n\
+    ............\n\
+    ........A...\n\
+    .........A..\n\
+    ............\n\
+    ............";
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = (AntennaMap, Grid<char>);
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_1: Some("14"),
+        part_2: Some("34"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
 }
 
+crate::example_tests!(Day8);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = "\
-        ............\n\
-        ........0...\n\
-        .....0......\n\
-        .......0....\n\
-        ....0.......\n\
-        ......A.....\n\
-        ............\n\
-        ............\n\
-        ........A...\n\
-        .........A..\n\
-        ............\n\
-        ............";
-
     #[test]
     fn test_calculate_antinode() {
         assert_eq!((3, 1), calculate_antinode(&(4, 3), &(5, 5)));
@@ -152,12 +161,31 @@ mod tests {
     }
 
     #[test]
-    fn test_part_1() {
-        assert_eq!(14, part_1(EXAMPLE));
+    fn test_wide_map() {
+        let input = parse_input(
+            "\
+            ..........\n\
+            ...a......\n\
+            ....a.....",
+        )
+        .unwrap();
+        assert_eq!(1, part_1(&input));
+        assert_eq!(3, part_2(&input));
     }
 
     #[test]
-    fn test_part_2() {
-        assert_eq!(34, part_2(EXAMPLE));
+    fn test_tall_map() {
+        let input = parse_input(
+            "\
+            ...\n\
+            .a.\n\
+            .a.\n\
+            ...\n\
+            ...\n\
+            ...",
+        )
+        .unwrap();
+        assert_eq!(2, part_1(&input));
+        assert_eq!(6, part_2(&input));
     }
 }
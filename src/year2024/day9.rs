@@ -1,11 +1,14 @@
 //! https://adventofcode.com/2024/day/9
 
+use crate::parse::{lines, ParseError};
+use crate::solution::{Answer, Example, Solution};
+use itertools::Itertools;
 use num_traits::{cast, NumCast};
 
 type Id = usize;
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
-enum DiskBlock {
+pub enum DiskBlock {
     File(Id),
     Free(),
 }
@@ -84,11 +87,42 @@ where
     }
 }
 
-fn parse_input(input: &str) -> Vec<DiskBlock> {
-    input
-        .chars()
-        .filter_map(|c| c.to_digit(10))
-        .into_disk_blocks()
+fn parse_input(input: &str) -> Result<Vec<DiskBlock>, ParseError> {
+    let sizes = lines(input)
+        .flat_map(|line| {
+            line.text
+                .char_indices()
+                .filter(|(_, c)| !c.is_whitespace())
+                .map(move |(i, c)| {
+                    c.to_digit(10).ok_or_else(|| {
+                        line.error(&line.text[i..i + c.len_utf8()], "expected a digit")
+                    })
+                })
+        })
+        .collect::<Result<Vec<u32>, _>>()?;
+    Ok(sizes.into_iter().into_disk_blocks().collect())
+}
+
+/// A contiguous run of identical blocks
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+struct Span {
+    block: DiskBlock,
+    start: usize,
+    len: usize,
+}
+
+fn into_spans(blocks: &[DiskBlock]) -> Vec<Span> {
+    let mut start = 0;
+    blocks
+        .iter()
+        .chunk_by(|block| **block)
+        .into_iter()
+        .map(|(block, group)| {
+            let len = group.count();
+            let span = Span { block, start, len };
+            start += len;
+            span
+        })
         .collect()
 }
 
@@ -103,13 +137,11 @@ fn checksum(blocks: &[DiskBlock]) -> usize {
         .sum()
 }
 
-fn part_1(input: &str) -> usize {
-    let original = parse_input(input);
-
+fn part_1(original: &[DiskBlock]) -> usize {
     let mut result = Vec::with_capacity(original.len());
 
-    let mut remaining = &original[..];
-    while remaining.len() > 0 {
+    let mut remaining = original;
+    while !remaining.is_empty() {
         match &remaining[0] {
             // If the left block is filled, add it to result
             DiskBlock::File(id) => {
@@ -117,13 +149,10 @@ fn part_1(input: &str) -> usize {
                 remaining = &remaining[1..];
             }
             DiskBlock::Free() => {
-                match &remaining[remaining.len() - 1] {
-                    // If the right block is filled, add it to the result
-                    DiskBlock::File(id) => {
-                        result.push(DiskBlock::File(*id));
-                        remaining = &remaining[1..];
-                    }
-                    _ => {}
+                // If the right block is filled, add it to the result
+                if let DiskBlock::File(id) = &remaining[remaining.len() - 1] {
+                    result.push(DiskBlock::File(*id));
+                    remaining = &remaining[1..];
                 }
                 remaining = &remaining[0..remaining.len() - 1];
             }
@@ -133,16 +162,67 @@ fn part_1(input: &str) -> usize {
     checksum(&result)
 }
 
-pub fn run(input: &str) {
-    println!("part 1 solution: {}", part_1(input));
+fn part_2(original: &[DiskBlock]) -> usize {
+    let (mut files, mut free): (Vec<Span>, Vec<Span>) = into_spans(original)
+        .into_iter()
+        .partition(|span| matches!(span.block, DiskBlock::File(_)));
+
+    // Files are in ascending Id order, so try to move them in reverse
+    for file in files.iter_mut().rev() {
+        // Only free space to the left of the file is a candidate.
+        // The space a file leaves behind is never used,
+        // as all the remaining files are to the left of it
+        if let Some(space) = free
+            .iter_mut()
+            .take_while(|space| space.start < file.start)
+            .find(|space| space.len >= file.len)
+        {
+            file.start = space.start;
+            space.start += file.len;
+            space.len -= file.len;
+        }
+    }
+
+    let mut result = vec![DiskBlock::Free(); original.len()];
+    for file in files {
+        result[file.start..file.start + file.len].fill(file.block);
+    }
+
+    checksum(&result)
+}
+
+const EXAMPLE: &str = "2333133121414131402";
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<DiskBlock>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_1: Some("1928"),
+        part_2: Some("2858"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
 }
 
+crate::example_tests!(Day9);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = "2333133121414131402";
-
     #[test]
     fn test_parse_input() {
         assert_eq!(
@@ -163,12 +243,47 @@ mod tests {
                 DiskBlock::File(2),
                 DiskBlock::File(2),
             ],
-            parse_input("12345")
+            parse_input("12345").unwrap()
+        )
+    }
+
+    #[test]
+    fn test_into_spans() {
+        assert_eq!(
+            vec![
+                Span {
+                    block: DiskBlock::File(0),
+                    start: 0,
+                    len: 1,
+                },
+                Span {
+                    block: DiskBlock::Free(),
+                    start: 1,
+                    len: 2,
+                },
+                Span {
+                    block: DiskBlock::File(1),
+                    start: 3,
+                    len: 3,
+                },
+                Span {
+                    block: DiskBlock::Free(),
+                    start: 6,
+                    len: 4,
+                },
+                Span {
+                    block: DiskBlock::File(2),
+                    start: 10,
+                    len: 5,
+                },
+            ],
+            into_spans(&parse_input("12345").unwrap())
         )
     }
 
     #[test]
-    fn test_part_1() {
-        assert_eq!(1928, part_1(EXAMPLE));
+    fn test_part_2() {
+        // Nothing fits to the left of any file
+        assert_eq!(132, part_2(&parse_input("12345").unwrap()));
     }
 }
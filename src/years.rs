@@ -0,0 +1,64 @@
+use crate::solution::Solver;
+use crate::year2024;
+use phf::phf_map;
+
+/// The solutions for a year, keyed by day
+pub type Days = phf::Map<u8, &'static dyn Solver>;
+
+/// Every year with solutions, each a module exposing its `DAYS`
+pub static YEARS: phf::Map<u16, &'static Days> = phf_map! {
+    2024_u16 => &year2024::DAYS,
+};
+
+/// The most recent year with solutions
+pub fn latest_year() -> u16 {
+    YEARS.keys().copied().max().unwrap()
+}
+
+pub fn get_days(year: u16) -> Option<&'static Days> {
+    YEARS.get(&year).copied()
+}
+
+pub fn get_solver(year: u16, day: u8) -> Option<&'static dyn Solver> {
+    get_days(year)?.get(&day).copied()
+}
+
+/// Parse a year, checking there are solutions for it
+pub fn parse_year(s: &str) -> Result<u16, String> {
+    let year = s.parse().map_err(|_| format!("invalid year {}", s))?;
+    match get_days(year) {
+        Some(_) => Ok(year),
+        None => {
+            let mut years: Vec<_> = YEARS.keys().map(u16::to_string).collect();
+            years.sort();
+            Err(format!(
+                "unknown year {}, expected one of {}",
+                year,
+                years.join(", ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_year() {
+        assert_eq!(Ok(2024), parse_year("2024"));
+        assert_eq!(
+            Err("unknown year 2014, expected one of 2024".to_string()),
+            parse_year("2014")
+        );
+        assert_eq!(Err("invalid year twenty".to_string()), parse_year("twenty"));
+    }
+
+    #[test]
+    fn test_get_solver() {
+        assert_eq!(2024, latest_year());
+        assert!(get_solver(2024, 1).is_some());
+        assert!(get_solver(2024, 26).is_none());
+        assert!(get_solver(2014, 1).is_none());
+    }
+}